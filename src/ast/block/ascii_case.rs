@@ -0,0 +1,22 @@
+//! ASCII case-insensitive comparison & hashing, shared by [`BlockName`][super::BlockName] and
+//! [`AttributeName`][super::AttributeName].
+
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+pub(super) fn cmp(a: &str, b: &str) -> Ordering {
+    a.bytes()
+        .map(|byte| byte.to_ascii_lowercase())
+        .cmp(b.bytes().map(|byte| byte.to_ascii_lowercase()))
+}
+
+pub(super) fn hash<H: Hasher>(str: &str, state: &mut H) {
+    for byte in str.bytes() {
+        byte.to_ascii_lowercase().hash(state);
+    }
+
+    // Same terminator as `str`, so that `("ab", "c")` and `("a", "bc")` hash differently.
+    0xff_u8.hash(state);
+}
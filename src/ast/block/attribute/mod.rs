@@ -5,4 +5,5 @@ pub use self::value::{AttributeValue, InvalidAttributeValue};
 pub type Attribute<'a> = (AttributeName<'a>, Option<AttributeValue<'a>>);
 
 mod name;
+mod reference;
 mod value;
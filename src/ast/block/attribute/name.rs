@@ -1,10 +1,13 @@
 use std::{
-    borrow::{Borrow, Cow},
+    borrow::Cow,
+    cmp::Ordering,
     fmt::Display,
+    hash::{Hash, Hasher},
     ops::Deref,
 };
 
 pub use self::error::InvalidAttributeName;
+use crate::ast::block::ascii_case;
 
 mod error {
     use std::error::Error;
@@ -25,7 +28,10 @@ mod error {
 }
 
 /// The name of an attribute, i.e: `lang` in `<script lang="ts">`.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+///
+/// The original casing is preserved, but comparisons (including with `str`) and hashing are ASCII
+/// case-insensitive, i.e: `LANG` is equal to `lang`.
+#[derive(Clone, Debug, Default)]
 #[must_use]
 pub struct AttributeName<'a>(Cow<'a, str>);
 
@@ -41,7 +47,7 @@ impl<'a> AttributeName<'a> {
     /// - `U+002F SOLIDUS (/)`
     /// - `U+003D EQUAL SIGN (=)`
     /// - `U+003E GREATER-THAN SIGN (>)`.
-    pub fn from_cow(mut src: Cow<'a, str>) -> Result<Self, InvalidAttributeName> {
+    pub fn from_cow(src: Cow<'a, str>) -> Result<Self, InvalidAttributeName> {
         if let Some(ch) = src.chars().find(|ch| {
             matches!(
                 ch,
@@ -57,13 +63,7 @@ impl<'a> AttributeName<'a> {
             return Err(InvalidAttributeName(ch));
         }
 
-        if src.contains(|ch: char| ch.is_ascii_uppercase()) {
-            src.to_mut().make_ascii_lowercase();
-
-            Ok(Self(src))
-        } else {
-            Ok(Self(src))
-        }
+        Ok(Self(src))
     }
 
     /// Convert a string into an [`AttributeName`] **without** validating
@@ -91,6 +91,16 @@ impl<'a> AttributeName<'a> {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Convert into a [`AttributeName`] which owns its data, allocating if it is borrowed.
+    pub fn into_owned(self) -> AttributeName<'static> {
+        AttributeName(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Same as [`AttributeName::into_owned`], without consuming `self`.
+    pub fn to_static(&self) -> AttributeName<'static> {
+        AttributeName(Cow::Owned(self.as_str().to_owned()))
+    }
 }
 
 impl Deref for AttributeName<'_> {
@@ -101,9 +111,41 @@ impl Deref for AttributeName<'_> {
     }
 }
 
-impl Borrow<str> for AttributeName<'_> {
-    fn borrow(&self) -> &str {
-        self.as_str()
+impl PartialEq for AttributeName<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl Eq for AttributeName<'_> {}
+
+impl PartialEq<str> for AttributeName<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for AttributeName<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialOrd for AttributeName<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AttributeName<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        ascii_case::cmp(self.as_str(), other.as_str())
+    }
+}
+
+impl Hash for AttributeName<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ascii_case::hash(self.as_str(), state);
     }
 }
 
@@ -0,0 +1,238 @@
+//! Character references in attribute values.
+//!
+//! See <https://html.spec.whatwg.org/multipage/parsing.html#character-reference-state>.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+/// Length of the longest named character reference, without the leading `&`.
+const MAX_NAME_LEN: usize = 32;
+
+/// Replacements of numeric character references in the `0x80..=0x9F` range.
+///
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#numeric-character-reference-end-state>.
+const C1_REPLACEMENTS: [(u32, char); 27] = [
+    (0x80, '\u{20AC}'),
+    (0x82, '\u{201A}'),
+    (0x83, '\u{0192}'),
+    (0x84, '\u{201E}'),
+    (0x85, '\u{2026}'),
+    (0x86, '\u{2020}'),
+    (0x87, '\u{2021}'),
+    (0x88, '\u{02C6}'),
+    (0x89, '\u{2030}'),
+    (0x8A, '\u{0160}'),
+    (0x8B, '\u{2039}'),
+    (0x8C, '\u{0152}'),
+    (0x8E, '\u{017D}'),
+    (0x91, '\u{2018}'),
+    (0x92, '\u{2019}'),
+    (0x93, '\u{201C}'),
+    (0x94, '\u{201D}'),
+    (0x95, '\u{2022}'),
+    (0x96, '\u{2013}'),
+    (0x97, '\u{2014}'),
+    (0x98, '\u{02DC}'),
+    (0x99, '\u{2122}'),
+    (0x9A, '\u{0161}'),
+    (0x9B, '\u{203A}'),
+    (0x9C, '\u{0153}'),
+    (0x9E, '\u{017E}'),
+    (0x9F, '\u{0178}'),
+];
+
+/// Named character references, without the leading `&`.
+fn named_references() -> &'static HashMap<&'static str, &'static str> {
+    static NAMED_REFERENCES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+    NAMED_REFERENCES.get_or_init(|| {
+        entities::ENTITIES
+            .iter()
+            .map(|entity| (&entity.entity[1..], entity.characters))
+            .collect()
+    })
+}
+
+/// Decoded form of a character reference, see [`reference_at`].
+enum Decoded {
+    Named(&'static str),
+    Numeric(char),
+}
+
+/// If `input` starts with a character reference (as found in an attribute value), returns its
+/// decoded form along with its length in bytes.
+fn reference_at(input: &str) -> Option<(Decoded, usize)> {
+    let rest = input.strip_prefix('&')?;
+
+    if let Some(rest) = rest.strip_prefix('#') {
+        let (hex, digits) = match rest.strip_prefix(['x', 'X']) {
+            Some(digits) => (true, digits),
+            None => (false, rest),
+        };
+
+        let len = digits
+            .find(|ch: char| {
+                !if hex {
+                    ch.is_ascii_hexdigit()
+                } else {
+                    ch.is_ascii_digit()
+                }
+            })
+            .unwrap_or(digits.len());
+
+        if len == 0 {
+            return None;
+        }
+
+        let code =
+            u32::from_str_radix(&digits[..len], if hex { 16 } else { 10 }).unwrap_or(u32::MAX);
+
+        let ch = match code {
+            0 => '\u{FFFD}',
+            0x80..=0x9F => C1_REPLACEMENTS
+                .iter()
+                .find_map(|&(other, ch)| (other == code).then_some(ch))
+                .unwrap_or_else(|| char::from_u32(code).unwrap_or('\u{FFFD}')),
+            _ => char::from_u32(code).unwrap_or('\u{FFFD}'),
+        };
+
+        let semicolon = usize::from(digits[len..].starts_with(';'));
+
+        return Some((
+            Decoded::Numeric(ch),
+            input.len() - digits.len() + len + semicolon,
+        ));
+    }
+
+    let candidate_len = rest
+        .find(|ch: char| !ch.is_ascii_alphanumeric())
+        .unwrap_or(rest.len())
+        .min(MAX_NAME_LEN);
+
+    if candidate_len == 0 {
+        return None;
+    }
+
+    let candidate = if rest[candidate_len..].starts_with(';') {
+        &rest[..=candidate_len]
+    } else {
+        &rest[..candidate_len]
+    };
+
+    let references = named_references();
+
+    let (name, characters) = (1..=candidate.len()).rev().find_map(|len| {
+        let name = &candidate[..len];
+        references.get(name).map(|characters| (name, *characters))
+    })?;
+
+    // For historical reasons, a reference without a trailing semicolon is not decoded when
+    // followed by `=` or an ASCII alphanumeric.
+    if !name.ends_with(';')
+        && rest[name.len()..].starts_with(|ch: char| ch == '=' || ch.is_ascii_alphanumeric())
+    {
+        return None;
+    }
+
+    Some((Decoded::Named(characters), 1 + name.len()))
+}
+
+/// Decode the character references of an attribute value, returns `None` if there is none.
+pub(super) fn decode(input: &str) -> Option<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    let mut decoded = false;
+
+    while let Some(index) = rest.find('&') {
+        output.push_str(&rest[..index]);
+        rest = &rest[index..];
+
+        match reference_at(rest) {
+            Some((reference, len)) => {
+                decoded = true;
+
+                match reference {
+                    Decoded::Named(characters) => output.push_str(characters),
+                    Decoded::Numeric(ch) => output.push(ch),
+                }
+
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+
+    decoded.then_some(output)
+}
+
+/// Encode an attribute value, so that [`decode`] returns it unchanged.
+///
+/// Only `&` starting a character reference are encoded.
+pub(super) fn encode(input: &str) -> Option<String> {
+    let mut indices = input
+        .match_indices('&')
+        .map(|(index, _)| index)
+        .filter(|&index| reference_at(&input[index..]).is_some())
+        .peekable();
+
+    indices.peek()?;
+
+    let mut output = String::with_capacity(input.len() + 4);
+    let mut offset = 0;
+
+    for index in indices {
+        output.push_str(&input[offset..index]);
+        output.push_str("&amp;");
+        offset = index + 1;
+    }
+
+    output.push_str(&input[offset..]);
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+
+    #[test]
+    fn test_decode() {
+        for (input, expected) in [
+            ("en&amp;us", "en&us"),
+            ("a &lt;b&gt;", "a <b>"),
+            ("&amp", "&"),
+            ("&ampx", "&ampx"),
+            ("&amp=", "&amp="),
+            ("&notit;", "&notit;"),
+            ("&not;it", "¬it"),
+            ("&unknown; & &", "&unknown; & &"),
+            ("&#65;&#x42;&#X43", "ABC"),
+            ("&#0;&#x110000;&#xD800;", "\u{FFFD}\u{FFFD}\u{FFFD}"),
+            ("&#x80;&#x81;", "\u{20AC}\u{81}"),
+            ("&#;&#x;", "&#;&#x;"),
+            ("&#99999999999999999999;", "\u{FFFD}"),
+        ] {
+            assert_eq!(
+                decode(input).as_deref().unwrap_or(input),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("a && b"), None);
+        assert_eq!(encode("en&lt").as_deref(), Some("en&amp;lt"));
+        assert_eq!(encode("&amp;").as_deref(), Some("&amp;amp;"));
+
+        for input in ["en&us", "&amp;", "&#65", "a && b", "&lt&gt"] {
+            let encoded = encode(input).unwrap_or_else(|| input.to_owned());
+            assert_eq!(decode(&encoded).as_deref().unwrap_or(&encoded), input);
+        }
+    }
+}
@@ -1,9 +1,13 @@
 use std::{
     borrow::{Borrow, Cow},
+    cmp::Ordering,
     fmt::Display,
+    hash::{Hash, Hasher},
     ops::Deref,
 };
 
+use super::reference;
+
 pub use self::error::InvalidAttributeValue;
 
 mod error {
@@ -25,34 +29,86 @@ mod error {
 }
 
 /// The value of an attribute, i.e: `ts` in `<script lang="ts">`.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+///
+/// Character references are decoded, i.e: `en&amp;us` is `en&us`, see [`AttributeValue::raw`]
+/// for the encoded form. Comparisons and hashing only consider the decoded value.
+#[derive(Clone, Debug, Default)]
 #[must_use]
-pub struct AttributeValue<'a>(Cow<'a, str>);
+pub struct AttributeValue<'a> {
+    value: Cow<'a, str>,
+    /// Encoded form, if different from `value`.
+    raw: Option<Cow<'a, str>>,
+}
 
 impl<'a> AttributeValue<'a> {
     /// Attempts to convert a string to an [`AttributeValue`].
     ///
+    /// Any string is a valid value: when printed, a value containing both a
+    /// `U+0022 QUOTATION MARK (")` and an `U+0027 APOSTROPHE (')` has its enclosing quote
+    /// encoded, i.e: `"it's a &quot;x&quot;"`.
+    ///
     /// # Errors
-    /// If the string contains both a `U+0022 QUOTATION MARK (")` and an `U+0027 APOSTROPHE (')`,
-    /// it also cannot contain any of the following characters:
-    /// - `U+0009 CHARACTER TABULATION`
-    /// - `U+000A LINE FEED`
-    /// - `U+000C FORM FEED`
-    /// - `U+0020 SPACE`
-    /// - `U+003E GREATER-THAN SIGN (>)`.
+    /// Never fails, the [`Result`] is kept for compatibility.
     pub fn from_cow(src: Cow<'a, str>) -> Result<Self, InvalidAttributeValue> {
-        if src.contains('\u{0022}') && src.contains('\u{0027}') {
-            if let Some(ch) = src.chars().find(|ch| {
-                matches!(
-                    ch,
-                    '\u{0009}' | '\u{000A}' | '\u{000C}' | '\u{0020}' | '\u{003E}'
-                )
-            }) {
-                return Err(InvalidAttributeValue(ch));
+        let raw = reference::encode(&src).map(Cow::Owned);
+
+        Ok(Self { value: src, raw })
+    }
+
+    /// Attempts to convert an encoded string (as found in a start tag, between quotes) to an
+    /// [`AttributeValue`], decoding its character references.
+    ///
+    /// # Errors
+    /// Never fails, see [`AttributeValue::from_cow`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use vue_sfc::AttributeValue;
+    ///
+    /// let value = AttributeValue::from_raw_cow(Cow::Borrowed("en&amp;us")).unwrap();
+    ///
+    /// assert_eq!(value.as_str(), "en&us");
+    /// assert_eq!(value.raw(), "en&amp;us");
+    /// ```
+    pub fn from_raw_cow(src: Cow<'a, str>) -> Result<Self, InvalidAttributeValue> {
+        let Self { value: raw, .. } = Self::from_cow(src)?;
+
+        Ok(Self::decode(raw))
+    }
+
+    /// Convert an encoded string into an [`AttributeValue`] **without** validating
+    /// (unless `debug_assertions` is enabled).
+    ///
+    /// # Panics
+    /// If `debug_assertions` is enabled, validate the input and panic on failure.
+    ///
+    /// # Safety
+    /// See string prerequisites of [`AttributeValue::from_raw_cow`].
+    pub unsafe fn from_raw_cow_unchecked(src: Cow<'a, str>) -> Self {
+        if cfg!(debug_assertions) {
+            match Self::from_raw_cow(src) {
+                Ok(val) => val,
+                Err(err) => {
+                    panic!("AttributeValue::from_raw_cow_unchecked(): {err}")
+                }
             }
+        } else {
+            Self::decode(src)
         }
+    }
 
-        Ok(Self(src))
+    fn decode(raw: Cow<'a, str>) -> Self {
+        match reference::decode(&raw) {
+            Some(value) => Self {
+                value: Cow::Owned(value),
+                raw: Some(raw),
+            },
+            None => Self {
+                value: raw,
+                raw: None,
+            },
+        }
     }
 
     /// Convert a string into an [`AttributeValue`] **without** validating
@@ -72,13 +128,94 @@ impl<'a> AttributeValue<'a> {
                 }
             }
         } else {
-            Self(src)
+            let raw = reference::encode(&src).map(Cow::Owned);
+
+            Self { value: src, raw }
         }
     }
 
+    /// The decoded value.
     #[must_use]
     pub fn as_str(&self) -> &str {
-        &self.0
+        &self.value
+    }
+
+    /// The encoded value, as found in the source (if parsed) or with `&` encoded as needed.
+    #[must_use]
+    pub fn raw(&self) -> &str {
+        self.raw.as_deref().unwrap_or(&self.value)
+    }
+
+    /// Convert into an [`AttributeValue`] which owns its data, allocating if it is borrowed.
+    pub fn into_owned(self) -> AttributeValue<'static> {
+        AttributeValue {
+            value: Cow::Owned(self.value.into_owned()),
+            raw: self.raw.map(|raw| Cow::Owned(raw.into_owned())),
+        }
+    }
+
+    /// Same as [`AttributeValue::into_owned`], without consuming `self`.
+    pub fn to_static(&self) -> AttributeValue<'static> {
+        self.clone().into_owned()
+    }
+
+    /// Write the encoded value surrounded by `preferred` quotes, falling back to the other quote
+    /// if the value contains `preferred`, or encoding `preferred` if it contains both.
+    pub(crate) fn write_quoted(
+        &self,
+        f: &mut impl std::fmt::Write,
+        preferred: char,
+    ) -> std::fmt::Result {
+        let raw = self.raw();
+        let other = if preferred == '\u{0022}' {
+            '\u{0027}'
+        } else {
+            '\u{0022}'
+        };
+
+        if !raw.contains(preferred) {
+            write!(f, "{preferred}{raw}{preferred}")
+        } else if !raw.contains(other) {
+            write!(f, "{other}{raw}{other}")
+        } else {
+            let encoded = if preferred == '\u{0022}' {
+                "&quot;"
+            } else {
+                "&#39;"
+            };
+
+            write!(
+                f,
+                "{preferred}{}{preferred}",
+                raw.replace(preferred, encoded)
+            )
+        }
+    }
+}
+
+impl PartialEq for AttributeValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for AttributeValue<'_> {}
+
+impl PartialOrd for AttributeValue<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AttributeValue<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl Hash for AttributeValue<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
     }
 }
 
@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+
+use crate::{Attribute, AttributeName, AttributeValue, Block, BlockName, Section};
+
+pub use self::error::InvalidBlock;
+use self::error::InvalidBlockKind;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    use crate::parser::ParseError;
+
+    /// Returned when a [`Block`][crate::Block] would not parse back to itself once printed,
+    /// i.e: a `<script>` block whose content contains `</script>`.
+    #[derive(Debug)]
+    pub struct InvalidBlock(pub(super) InvalidBlockKind);
+
+    impl Display for InvalidBlock {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self(InvalidBlockKind::Parse(err)) => {
+                    write!(f, "printed block does not parse: {err}")
+                }
+                Self(InvalidBlockKind::RoundTrip) => {
+                    write!(f, "printed block parses to different sections")
+                }
+            }
+        }
+    }
+
+    impl Error for InvalidBlock {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self(InvalidBlockKind::Parse(err)) => Some(err),
+                Self(InvalidBlockKind::RoundTrip) => None,
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub(super) enum InvalidBlockKind {
+        Parse(ParseError),
+        RoundTrip,
+    }
+}
+
+impl<'a> Block<'a> {
+    /// Attempts to create a [`Block`] which, once printed, parses back to itself.
+    ///
+    /// Leading line breaks & trailing whitespace of `content` are trimmed, as
+    /// [`parse`][crate::parse] does.
+    ///
+    /// # Errors
+    /// Will return an error if the printed block does not parse, or parses to different
+    /// sections, i.e: if `content` contains the end tag of the block.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use vue_sfc::{Block, BlockName};
+    ///
+    /// let name = BlockName::try_from("script").unwrap();
+    ///
+    /// assert!(Block::new(name.clone(), Vec::new(), Cow::Borrowed("let a = 1;")).is_ok());
+    /// assert!(Block::new(name, Vec::new(), Cow::Borrowed("'</script>'")).is_err());
+    /// ```
+    pub fn new(
+        name: BlockName<'a>,
+        attributes: Vec<Attribute<'a>>,
+        content: Cow<'a, str>,
+    ) -> Result<Self, InvalidBlock> {
+        let content = match content {
+            Cow::Borrowed(content) => Cow::Borrowed(trim(content)),
+            Cow::Owned(content) => match trim(&content) {
+                trimmed if trimmed.len() == content.len() => Cow::Owned(content),
+                trimmed => Cow::Owned(trimmed.to_owned()),
+            },
+        };
+
+        let block = Self {
+            name,
+            attributes,
+            content,
+        };
+
+        let printed = block.to_string();
+
+        match crate::parse(&printed) {
+            Ok(sections) => match sections.as_slice() {
+                [Section::Block(parsed)] if *parsed == block => Ok(block),
+                _ => Err(InvalidBlock(InvalidBlockKind::RoundTrip)),
+            },
+            Err(err) => Err(InvalidBlock(InvalidBlockKind::Parse(err))),
+        }
+    }
+
+    /// Returns a [`BlockBuilder`] for a block named `name`.
+    pub fn builder(name: BlockName<'a>) -> BlockBuilder<'a> {
+        BlockBuilder {
+            name,
+            attributes: Vec::new(),
+            content: Cow::Borrowed(""),
+        }
+    }
+}
+
+/// Same as `parse`'s trimming of block contents.
+fn trim(content: &str) -> &str {
+    content.trim_start_matches(['\n', '\r']).trim_end()
+}
+
+/// Builds a [`Block`], see [`Block::builder`].
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::{AttributeName, AttributeValue, Block, BlockName};
+///
+/// let block = Block::builder(BlockName::try_from("style").unwrap())
+///     .attribute(AttributeName::try_from("lang").unwrap(), AttributeValue::try_from("scss").ok())
+///     .attribute(AttributeName::try_from("scoped").unwrap(), None)
+///     .content("a {}")
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(block.to_string(), "<style lang=\"scss\" scoped>\na {}\n</style>");
+/// ```
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct BlockBuilder<'a> {
+    name: BlockName<'a>,
+    attributes: Vec<Attribute<'a>>,
+    content: Cow<'a, str>,
+}
+
+impl<'a> BlockBuilder<'a> {
+    /// Append an attribute.
+    pub fn attribute(mut self, name: AttributeName<'a>, value: Option<AttributeValue<'a>>) -> Self {
+        self.attributes.push((name, value));
+        self
+    }
+
+    pub fn content(mut self, content: impl Into<Cow<'a, str>>) -> Self {
+        self.content = content.into();
+        self
+    }
+
+    /// See [`Block::new`].
+    ///
+    /// # Errors
+    /// See [`Block::new`].
+    pub fn build(self) -> Result<Block<'a>, InvalidBlock> {
+        Block::new(self.name, self.attributes, self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{AttributeName, AttributeValue, Block, BlockName};
+
+    #[test]
+    fn test_new() {
+        let block = |name: &str, content: &'static str| {
+            Block::new(
+                BlockName::try_from(name).unwrap().into_owned(),
+                Vec::new(),
+                Cow::Borrowed(content),
+            )
+        };
+
+        assert_eq!(block("script", "\n\na\n\n").unwrap().content, "a");
+        assert!(block("template", "<template><p>a</p></template>").is_ok());
+        assert!(block("script", "'<script>'").is_ok());
+
+        assert!(block("script", "a</script>b").is_err());
+        assert!(block("template", "</template>").is_err());
+        assert!(block("template", "<template>").is_err());
+
+        // `<` in attribute values.
+        let title = |value: &str| {
+            vec![(
+                AttributeName::try_from("title").unwrap(),
+                Some(AttributeValue::try_from(value.to_owned()).unwrap()),
+            )]
+        };
+
+        for name in ["script", "template"] {
+            let end_tag = format!("</{name}>");
+
+            for (value, content) in [(end_tag.as_str(), "x"), ("<template>", "x"), ("</a>", "")] {
+                let block = Block::new(
+                    BlockName::try_from(name).unwrap(),
+                    title(value),
+                    Cow::Borrowed(content),
+                );
+
+                assert!(block.is_ok(), "{name} {value}");
+            }
+        }
+    }
+}
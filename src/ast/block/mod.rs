@@ -3,13 +3,23 @@ use std::{borrow::Cow, fmt::Display};
 pub use self::attribute::{
     Attribute, AttributeName, AttributeValue, InvalidAttributeName, InvalidAttributeValue,
 };
+pub use self::builder::{BlockBuilder, InvalidBlock};
 pub use self::name::{BlockName, InvalidBlockName};
 
+mod ascii_case;
 mod attribute;
+mod builder;
 mod name;
 
 /// A block as defined in the [SFC specifications][1].
 ///
+/// The alternate flag of [`Display`] (`{:#}`) prints empty blocks as self-closing tags, i.e:
+/// `<style src="./a.css" />`.
+///
+/// [`Display`] does not escape `content`: a block built from its fields, i.e: a `script` block
+/// whose content contains `</script>`, may print as markup which does not parse back to it. Use
+/// [`Block::new`] to build blocks which are guaranteed to.
+///
 /// [1]: https://v3.vuejs.org/api/sfc-spec.html#language-blocks
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Block<'a> {
@@ -18,6 +28,64 @@ pub struct Block<'a> {
     pub content: Cow<'a, str>,
 }
 
+impl<'a> Block<'a> {
+    /// Returns the first attribute named `name` (compared ASCII case-insensitively).
+    ///
+    /// # Example
+    /// ```rust
+    /// let sfc = vue_sfc::parse("<script LANG=\"ts\" setup></script>").unwrap();
+    ///
+    /// if let vue_sfc::Section::Block(block) = &sfc[0] {
+    ///     assert!(block.attribute("setup").is_some());
+    ///     assert_eq!(block.attribute_value("lang").unwrap().as_str(), "ts");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&Attribute<'a>> {
+        self.attributes.iter().find(|(other, _)| other == name)
+    }
+
+    /// Returns the value of the first attribute named `name` (compared ASCII case-insensitively),
+    /// `None` if there is no such attribute or if it has no value.
+    #[must_use]
+    pub fn attribute_value(&self, name: &str) -> Option<&AttributeValue<'a>> {
+        self.attribute(name).and_then(|(_, value)| value.as_ref())
+    }
+
+    /// Convert into a [`Block`] which owns its data, allocating if it is borrowed.
+    #[must_use]
+    pub fn into_owned(self) -> Block<'static> {
+        Block {
+            name: self.name.into_owned(),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(|(name, value)| (name.into_owned(), value.map(AttributeValue::into_owned)))
+                .collect(),
+            content: Cow::Owned(self.content.into_owned()),
+        }
+    }
+
+    /// Same as [`Block::into_owned`], without consuming `self`.
+    #[must_use]
+    pub fn to_static(&self) -> Block<'static> {
+        Block {
+            name: self.name.to_static(),
+            attributes: self
+                .attributes
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_static(),
+                        value.as_ref().map(AttributeValue::to_static),
+                    )
+                })
+                .collect(),
+            content: Cow::Owned(self.content.clone().into_owned()),
+        }
+    }
+}
+
 impl Display for Block<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let Self {
@@ -31,11 +99,9 @@ impl Display for Block<'_> {
 
         for (name, value) in attributes {
             match value {
-                Some(value) if value.as_str().contains('\u{0022}') => {
-                    write!(f, " {name}='{value}'")?;
-                }
                 Some(value) => {
-                    write!(f, r#" {name}="{value}""#)?;
+                    write!(f, " {name}=")?;
+                    value.write_quoted(f, '\u{0022}')?;
                 }
                 None => {
                     write!(f, " {name}")?;
@@ -43,6 +109,10 @@ impl Display for Block<'_> {
             }
         }
 
+        if f.alternate() && content.is_empty() {
+            return write!(f, " />");
+        }
+
         write!(f, ">")?;
 
         if !content.is_empty() {
@@ -101,6 +171,32 @@ mod tests {
             r#"<script lang="ts" setup></script>"#
         );
 
+        assert_eq!(
+            Block {
+                name: BlockName::try_from("i18n").unwrap(),
+                attributes: vec![
+                    (
+                        AttributeName::try_from("locale").unwrap(),
+                        Some(AttributeValue::try_from("en&us").unwrap())
+                    ),
+                    (
+                        AttributeName::try_from("title").unwrap(),
+                        Some(AttributeValue::try_from("\"a\"").unwrap())
+                    ),
+                    (
+                        AttributeName::try_from("src").unwrap(),
+                        Some(
+                            AttributeValue::from_raw_cow(Cow::Borrowed("&#x2e;/a&period;json"))
+                                .unwrap()
+                        )
+                    )
+                ],
+                content: Cow::Borrowed("")
+            }
+            .to_string(),
+            r#"<i18n locale="en&us" title='"a"' src="&#x2e;/a&period;json"></i18n>"#
+        );
+
         assert_eq!(
             Block {
                 name: BlockName::try_from("style").unwrap(),
@@ -136,4 +232,29 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_display_self_closing() {
+        let block = Block {
+            name: BlockName::try_from("style").unwrap(),
+            attributes: vec![(
+                AttributeName::try_from("src").unwrap(),
+                Some(AttributeValue::try_from("./a.css").unwrap()),
+            )],
+            content: Cow::Borrowed(""),
+        };
+
+        assert_eq!(format!("{block:#}"), r#"<style src="./a.css" />"#);
+        assert_eq!(block.to_string(), r#"<style src="./a.css"></style>"#);
+
+        let block = Block {
+            content: Cow::Borrowed("a {}"),
+            ..block
+        };
+
+        assert_eq!(
+            format!("{block:#}"),
+            concat!("<style src=\"./a.css\">\n", "a {}\n", "</style>")
+        );
+    }
 }
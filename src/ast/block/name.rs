@@ -1,11 +1,14 @@
 use std::{
-    borrow::{Borrow, Cow},
+    borrow::Cow,
+    cmp::Ordering,
     fmt::Display,
+    hash::{Hash, Hasher},
     ops::Deref,
 };
 
 pub use self::error::InvalidBlockName;
 use self::error::InvalidBlockNameKind;
+use super::ascii_case;
 
 mod error {
     use std::error::Error;
@@ -38,7 +41,10 @@ mod error {
 }
 
 /// The name of a block, i.e: `script` in `<script lang="ts">`.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+///
+/// The original casing is preserved, but comparisons (including with `str`) and hashing are ASCII
+/// case-insensitive, i.e: `I18n` is equal to `i18n`.
+#[derive(Clone, Debug, Default)]
 #[must_use]
 pub struct BlockName<'a>(Cow<'a, str>);
 
@@ -55,7 +61,7 @@ impl<'a> BlockName<'a> {
     ///   - `U+0020 SPACE`,
     ///   - `U+002F SOLIDUS (/)`,
     ///   - `U+003E GREATER-THAN SIGN (>)`.
-    pub fn from_cow(mut src: Cow<'a, str>) -> Result<Self, InvalidBlockName> {
+    pub fn from_cow(src: Cow<'a, str>) -> Result<Self, InvalidBlockName> {
         if !src.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
             return Err(InvalidBlockName(
                 InvalidBlockNameKind::StartsWithNonAsciiAlpha,
@@ -71,10 +77,6 @@ impl<'a> BlockName<'a> {
             return Err(InvalidBlockName(InvalidBlockNameKind::IllegalChar(ch)));
         }
 
-        if src.contains(|ch: char| ch.is_ascii_uppercase()) {
-            src.to_mut().make_ascii_lowercase();
-        }
-
         Ok(Self(src))
     }
 
@@ -103,6 +105,16 @@ impl<'a> BlockName<'a> {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Convert into a [`BlockName`] which owns its data, allocating if it is borrowed.
+    pub fn into_owned(self) -> BlockName<'static> {
+        BlockName(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Same as [`BlockName::into_owned`], without consuming `self`.
+    pub fn to_static(&self) -> BlockName<'static> {
+        BlockName(Cow::Owned(self.as_str().to_owned()))
+    }
 }
 
 impl Display for BlockName<'_> {
@@ -119,9 +131,41 @@ impl Deref for BlockName<'_> {
     }
 }
 
-impl Borrow<str> for BlockName<'_> {
-    fn borrow(&self) -> &str {
-        self.as_str()
+impl PartialEq for BlockName<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl Eq for BlockName<'_> {}
+
+impl PartialEq<str> for BlockName<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for BlockName<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str().eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialOrd for BlockName<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BlockName<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        ascii_case::cmp(self.as_str(), other.as_str())
+    }
+}
+
+impl Hash for BlockName<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ascii_case::hash(self.as_str(), state);
     }
 }
 
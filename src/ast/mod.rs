@@ -1,10 +1,12 @@
 pub use self::block::{
-    Attribute, AttributeName, AttributeValue, Block, BlockName, InvalidAttributeName,
-    InvalidAttributeValue, InvalidBlockName,
+    Attribute, AttributeName, AttributeValue, Block, BlockBuilder, BlockName, InvalidAttributeName,
+    InvalidAttributeValue, InvalidBlock, InvalidBlockName,
 };
 pub use self::raw::{InvalidRaw, Raw};
 pub use self::section::Section;
+pub use self::sfc::{Sfc, SfcBuf};
 
 mod block;
 mod raw;
 mod section;
+mod sfc;
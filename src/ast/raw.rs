@@ -5,6 +5,7 @@ use std::{
 };
 
 pub use self::error::InvalidRaw;
+use self::error::InvalidRawKind;
 
 mod error {
     use std::error::Error;
@@ -12,15 +13,31 @@ mod error {
 
     /// Returned when a function was unable to convert a string to a [`Raw`][super::Raw].
     #[derive(Debug)]
-    pub struct InvalidRaw;
+    pub struct InvalidRaw(pub(super) InvalidRawKind);
 
     impl Display for InvalidRaw {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            write!(f, "raw section must not be empty once end-trimmed")
+            match self {
+                Self(InvalidRawKind::Empty) => {
+                    write!(f, "raw section must not be empty once trimmed")
+                }
+                Self(InvalidRawKind::TagLike(index)) => {
+                    write!(
+                        f,
+                        "raw section must not contain a tag, found one at {index}"
+                    )
+                }
+            }
         }
     }
 
     impl Error for InvalidRaw {}
+
+    #[derive(Debug)]
+    pub(super) enum InvalidRawKind {
+        Empty,
+        TagLike(usize),
+    }
 }
 
 /// Represent non-empty text before, after or between blocks.
@@ -31,20 +48,45 @@ pub struct Raw<'a>(Cow<'a, str>);
 impl<'a> Raw<'a> {
     /// Attempts to convert a string to a [`Raw`].
     ///
+    /// Leading line breaks & trailing whitespace are trimmed, as [`parse`][crate::parse] does.
+    ///
     /// # Errors
-    /// Will return an error if the string is empty once end-trimmed.
+    /// Will return an error if the string:
+    /// - is empty once trimmed,
+    /// - contains a `U+003C LESS-THAN SIGN (<)` followed by an ASCII alpha, or by a
+    ///   `U+002F SOLIDUS (/)` and an ASCII alpha, as it could be parsed as a tag once printed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use vue_sfc::Raw;
+    ///
+    /// assert_eq!(Raw::try_from("\n<!-- a -->\n").unwrap().as_str(), "<!-- a -->");
+    /// assert!(Raw::try_from("a < b").is_ok());
+    /// assert!(Raw::try_from("<!-- <template> -->").is_err());
+    /// ```
     pub fn from_cow(src: Cow<'a, str>) -> Result<Self, InvalidRaw> {
-        let trimmed = match src {
-            Cow::Borrowed(string) => Cow::Borrowed(string.trim_end()),
-            Cow::Owned(mut string) => {
-                string.truncate(string.trim_end().len());
+        let raw = Self::from_parsed(src)?;
 
-                Cow::Owned(string)
-            }
+        if let Some(index) = find_tag_like(&raw) {
+            return Err(InvalidRaw(InvalidRawKind::TagLike(index)));
+        }
+
+        Ok(raw)
+    }
+
+    /// Same as [`Raw::from_cow`], but allows tag-like text: the parser produces such raw sections
+    /// when a `<` does not start a valid tag, i.e: `<a` at the end of the input.
+    pub(crate) fn from_parsed(src: Cow<'a, str>) -> Result<Self, InvalidRaw> {
+        let trimmed = match src {
+            Cow::Borrowed(string) => Cow::Borrowed(trim(string)),
+            Cow::Owned(string) => match trim(&string) {
+                trimmed if trimmed.len() == string.len() => Cow::Owned(string),
+                trimmed => Cow::Owned(trimmed.to_owned()),
+            },
         };
 
         if trimmed.is_empty() {
-            return Err(InvalidRaw);
+            return Err(InvalidRaw(InvalidRawKind::Empty));
         }
 
         Ok(Self(trimmed))
@@ -75,6 +117,31 @@ impl<'a> Raw<'a> {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Convert into a [`Raw`] which owns its data, allocating if it is borrowed.
+    pub fn into_owned(self) -> Raw<'static> {
+        Raw(Cow::Owned(self.0.into_owned()))
+    }
+
+    /// Same as [`Raw::into_owned`], without consuming `self`.
+    pub fn to_static(&self) -> Raw<'static> {
+        Raw(Cow::Owned(self.as_str().to_owned()))
+    }
+}
+
+/// Same as `parse`'s trimming of raw sections.
+fn trim(src: &str) -> &str {
+    src.trim_start_matches(['\n', '\r']).trim_end()
+}
+
+/// Returns the offset of the first `<` which could open a start or end tag.
+fn find_tag_like(src: &str) -> Option<usize> {
+    memchr::memchr_iter(b'<', src.as_bytes()).find(|&index| {
+        let after = &src.as_bytes()[index + 1..];
+        let after = after.strip_prefix(b"/").unwrap_or(after);
+
+        after.first().is_some_and(u8::is_ascii_alphabetic)
+    })
 }
 
 impl Display for Raw<'_> {
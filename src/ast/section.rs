@@ -11,6 +11,56 @@ pub enum Section<'a> {
     Block(Block<'a>),
 }
 
+impl<'a> Section<'a> {
+    /// Returns the block, if this is a [`Section::Block`].
+    #[must_use]
+    pub fn as_block(&self) -> Option<&Block<'a>> {
+        match self {
+            Self::Block(block) => Some(block),
+            Self::Raw(_) => None,
+        }
+    }
+
+    /// Returns the raw section, if this is a [`Section::Raw`].
+    #[must_use]
+    pub fn as_raw(&self) -> Option<&Raw<'a>> {
+        match self {
+            Self::Raw(raw) => Some(raw),
+            Self::Block(_) => None,
+        }
+    }
+
+    /// Convert into a [`Section`] which owns its data, allocating if it is borrowed.
+    #[must_use]
+    pub fn into_owned(self) -> Section<'static> {
+        match self {
+            Self::Raw(raw) => Section::Raw(raw.into_owned()),
+            Self::Block(block) => Section::Block(block.into_owned()),
+        }
+    }
+
+    /// Same as [`Section::into_owned`], without consuming `self`.
+    #[must_use]
+    pub fn to_static(&self) -> Section<'static> {
+        match self {
+            Self::Raw(raw) => Section::Raw(raw.to_static()),
+            Self::Block(block) => Section::Block(block.to_static()),
+        }
+    }
+}
+
+impl<'a> From<Block<'a>> for Section<'a> {
+    fn from(block: Block<'a>) -> Self {
+        Self::Block(block)
+    }
+}
+
+impl<'a> From<Raw<'a>> for Section<'a> {
+    fn from(raw: Raw<'a>) -> Self {
+        Self::Raw(raw)
+    }
+}
+
 impl Display for Section<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
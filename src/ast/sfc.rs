@@ -0,0 +1,596 @@
+use std::{fmt::Display, ops::Deref};
+
+use crate::{
+    parser::{section_from_span, ParseError, ParseOptions, ParseWarning, SectionSpan, Sections},
+    Block, Raw, Section,
+};
+
+/// A whole Vue SFC: its sections, in order.
+///
+/// [`Display`] prints the sections separated by a blank line and followed by a newline, see
+/// [`format`][crate::format] for configurable printing.
+///
+/// Parsing the printed sections yields the same sections only if blocks were returned by
+/// [`parse`][crate::parse] or built with [`Block::new`], raw sections were returned by
+/// [`parse`][crate::parse] or built with [`Raw::from_cow`], and no two raw sections are adjacent
+/// (they are parsed back as a single one). Sections built from their fields are printed as is,
+/// with no such guarantee.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::Sfc;
+///
+/// let mut sfc = Sfc::parse("<template></template><!-- a --><script></script>").unwrap();
+///
+/// sfc.move_section(2, 0);
+/// sfc.retain(|section| section.as_raw().is_none());
+///
+/// assert_eq!(sfc.blocks().count(), 2);
+/// assert_eq!(sfc.to_string(), "<script></script>\n\n<template></template>\n");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Sfc<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Sfc<'a> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the given input, see [`parse`][crate::parse].
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        crate::parse(input).map(Self::from)
+    }
+
+    #[must_use]
+    pub fn sections(&self) -> &[Section<'a>] {
+        &self.sections
+    }
+
+    #[must_use]
+    pub fn into_sections(self) -> Vec<Section<'a>> {
+        self.sections
+    }
+
+    /// Blocks, in order.
+    pub fn blocks(&self) -> impl Iterator<Item = &Block<'a>> {
+        self.sections.iter().filter_map(Section::as_block)
+    }
+
+    /// Blocks, in order.
+    pub fn blocks_mut(&mut self) -> impl Iterator<Item = &mut Block<'a>> {
+        self.sections
+            .iter_mut()
+            .filter_map(|section| match section {
+                Section::Block(block) => Some(block),
+                Section::Raw(_) => None,
+            })
+    }
+
+    /// Blocks named `name` (compared ASCII case-insensitively), in order.
+    pub fn blocks_named<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s Block<'a>> {
+        self.blocks().filter(move |block| block.name == name)
+    }
+
+    /// The first block named `name` (compared ASCII case-insensitively).
+    #[must_use]
+    pub fn block(&self, name: &str) -> Option<&Block<'a>> {
+        self.blocks().find(|block| block.name == name)
+    }
+
+    /// Raw sections, in order.
+    pub fn raws(&self) -> impl Iterator<Item = &Raw<'a>> {
+        self.sections.iter().filter_map(Section::as_raw)
+    }
+
+    pub fn push(&mut self, section: impl Into<Section<'a>>) {
+        self.sections.push(section.into());
+    }
+
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, section: impl Into<Section<'a>>) {
+        self.sections.insert(index, section.into());
+    }
+
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Section<'a> {
+        self.sections.remove(index)
+    }
+
+    /// Keep only the sections for which `f` returns `true`.
+    pub fn retain(&mut self, f: impl FnMut(&Section<'a>) -> bool) {
+        self.sections.retain(f);
+    }
+
+    /// # Panics
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.sections.swap(a, b);
+    }
+
+    /// Move the section at `from` so that it ends up at `to`, shifting the sections in between.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` are out of bounds.
+    pub fn move_section(&mut self, from: usize, to: usize) {
+        if from < to {
+            self.sections[from..=to].rotate_left(1);
+        } else {
+            self.sections[to..=from].rotate_right(1);
+        }
+    }
+
+    /// Stably sort blocks by the key returned by `f`.
+    ///
+    /// Raw sections move along with the block following them, trailing raw sections stay at
+    /// the end.
+    pub fn sort_blocks_by_key<K: Ord>(&mut self, mut f: impl FnMut(&Block<'a>) -> K) {
+        let mut groups = Vec::new();
+        let mut group = Vec::new();
+
+        for section in std::mem::take(&mut self.sections) {
+            let is_block = matches!(section, Section::Block(_));
+            group.push(section);
+
+            if is_block {
+                groups.push(std::mem::take(&mut group));
+            }
+        }
+
+        groups.sort_by_cached_key(|group: &Vec<Section<'a>>| match group.last() {
+            Some(Section::Block(block)) => f(block),
+            _ => unreachable!("groups end with a block"),
+        });
+
+        self.sections = groups.into_iter().flatten().chain(group).collect();
+    }
+
+    /// Convert into an [`Sfc`] which owns its data, see [`Section::into_owned`].
+    #[must_use]
+    pub fn into_owned(self) -> Sfc<'static> {
+        self.sections.into_iter().map(Section::into_owned).collect()
+    }
+
+    /// Same as [`Sfc::into_owned`], without consuming `self`.
+    #[must_use]
+    pub fn to_static(&self) -> Sfc<'static> {
+        self.sections.iter().map(Section::to_static).collect()
+    }
+}
+
+impl<'a> Deref for Sfc<'a> {
+    type Target = [Section<'a>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.sections
+    }
+}
+
+impl<'a> From<Vec<Section<'a>>> for Sfc<'a> {
+    fn from(sections: Vec<Section<'a>>) -> Self {
+        Self { sections }
+    }
+}
+
+impl<'a> FromIterator<Section<'a>> for Sfc<'a> {
+    fn from_iter<T: IntoIterator<Item = Section<'a>>>(iter: T) -> Self {
+        Self {
+            sections: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for Sfc<'a> {
+    type Item = Section<'a>;
+    type IntoIter = std::vec::IntoIter<Section<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sections.into_iter()
+    }
+}
+
+impl<'s, 'a> IntoIterator for &'s Sfc<'a> {
+    type Item = &'s Section<'a>;
+    type IntoIter = std::slice::Iter<'s, Section<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.sections.iter()
+    }
+}
+
+impl Display for Sfc<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, section) in self.sections.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+                writeln!(f)?;
+            }
+
+            section.fmt(f)?;
+        }
+
+        if !self.sections.is_empty() {
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A Vue SFC which owns its source, handing out [`Section`]s borrowing from it.
+///
+/// Useful to keep parsed components around, i.e: in a cache, without re-parsing them.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::{ast::SfcBuf, Section};
+///
+/// let sfc = SfcBuf::parse(String::from("<script>a</script>")).unwrap();
+///
+/// for section in sfc.sections() {
+///     if let Section::Block(block) = section {
+///         assert_eq!(block.content, "a");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SfcBuf {
+    source: String,
+    spans: Vec<SectionSpan>,
+    options: ParseOptions,
+    warnings: Vec<ParseWarning>,
+}
+
+impl SfcBuf {
+    /// Parse `source` as a Vue SFC.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse(source: String) -> Result<Self, ParseError> {
+        Self::parse_with_options(source, ParseOptions::default())
+    }
+
+    /// Same as [`SfcBuf::parse`], with the given options.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse_with_options(source: String, options: ParseOptions) -> Result<Self, ParseError> {
+        Self::try_parse(source, options).map_err(|(_, err)| err)
+    }
+
+    /// Same as [`SfcBuf::parse_with_options`], handing the source back on failure.
+    pub(crate) fn try_parse(
+        source: String,
+        options: ParseOptions,
+    ) -> Result<Self, (String, ParseError)> {
+        let mut iter = Sections::with_options(&source, options.clone());
+        let mut spans = Vec::new();
+
+        loop {
+            match iter.next_spanned() {
+                Ok(Some((_, span))) => spans.push(span),
+                Ok(None) => break,
+                Err(err) => return Err((source, err)),
+            }
+        }
+
+        let warnings = iter.warnings().to_vec();
+
+        Ok(Self {
+            source,
+            spans,
+            options,
+            warnings,
+        })
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The span of each section, in source order.
+    #[must_use]
+    pub fn spans(&self) -> &[SectionSpan] {
+        &self.spans
+    }
+
+    /// Recoverable errors reported while parsing.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Sections borrowing from the source, in source order.
+    ///
+    /// Only the start tags are parsed again, the content of sections is not re-scanned.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'_>> {
+        self.spans.iter().map(|span| {
+            match section_from_span(&self.source, span, &self.options, &mut Vec::new()) {
+                Ok(Some(section)) => section,
+                _ => unreachable!("spans were parsed from the source"),
+            }
+        })
+    }
+
+    /// Same as [`SfcBuf::sections`], collected in an [`Sfc`].
+    #[must_use]
+    pub fn sfc(&self) -> Sfc<'_> {
+        self.sections().collect()
+    }
+
+    #[must_use]
+    pub fn into_source(self) -> String {
+        self.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use proptest::prelude::*;
+
+    use crate::{
+        parser::{DuplicateAttributes, ParseOptions},
+        AttributeName, AttributeValue, Block, BlockName, Raw, Section,
+    };
+
+    use super::{Sfc, SfcBuf};
+
+    /// Concatenations of fragments likely to interact with the parser.
+    fn text(fragments: &'static [&'static str], max: usize) -> impl Strategy<Value = String> {
+        prop::collection::vec(prop::sample::select(fragments), 0..max)
+            .prop_map(|fragments| fragments.concat())
+    }
+
+    /// Attribute values & block contents, unvalidated.
+    const MARKUP: &[&str] = &[
+        "a",
+        " ",
+        "\n",
+        "\t",
+        "<",
+        ">",
+        "/",
+        "</",
+        "<!--",
+        "-->",
+        "\"",
+        "'",
+        "&",
+        "&amp;",
+        "&quot;",
+        "=",
+        "pug",
+        "<p>",
+        "</p>",
+        "<template>",
+        "</template>",
+        "<script>",
+        "</script>",
+    ];
+
+    /// Parts of a block, only validated by the name & attribute types.
+    fn block_parts() -> impl Strategy<
+        Value = (
+            BlockName<'static>,
+            Vec<(AttributeName<'static>, Option<AttributeValue<'static>>)>,
+            String,
+        ),
+    > {
+        let name = prop_oneof![
+            Just(String::from("template")),
+            Just(String::from("script")),
+            "[a-zA-Z][a-zA-Z0-9:-]{0,6}",
+        ]
+        .prop_map(|name| BlockName::try_from(name).unwrap());
+
+        let attribute = (
+            prop_oneof![
+                Just(String::from("lang")),
+                Just(String::from("title")),
+                "[a-zA-Z:@#.-][a-zA-Z0-9:@#.\"'<-]{0,6}"
+            ],
+            prop::option::of(text(MARKUP, 8)),
+        )
+            .prop_filter_map("invalid attribute", |(name, value)| {
+                Some((
+                    AttributeName::try_from(name).ok()?,
+                    match value {
+                        Some(value) => Some(AttributeValue::try_from(value).ok()?),
+                        None => None,
+                    },
+                ))
+            });
+
+        (
+            name,
+            prop::collection::vec(attribute, 0..4),
+            text(MARKUP, 24),
+        )
+    }
+
+    /// Whole Vue SFCs, valid or not.
+    fn input() -> impl Strategy<Value = String> {
+        text(
+            &[
+                "<template>",
+                "</template>",
+                "<template title=\"</template>\">",
+                "<script>",
+                "</script>",
+                "<script title='</script>'>",
+                "<a b=\"'<c>\">",
+                "<a b='\"</a>'>",
+                "</a>",
+                "<c/>",
+                "<!-- a -->",
+                "a",
+                "\n",
+                "<",
+                "&quot;",
+            ],
+            12,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_block_new((name, attributes, content) in block_parts()) {
+            // Either rejected, or printed as markup parsing back to the same block.
+            if let Ok(block) = Block::new(name, attributes, Cow::Owned(content)) {
+                let printed = block.to_string();
+
+                prop_assert_eq!(crate::parse(&printed).unwrap(), vec![Section::Block(block)]);
+            }
+        }
+
+        #[test]
+        fn test_attribute_value_quotes(value in text(MARKUP, 8)) {
+            // Values containing both quotes & whitespace are accepted and survive printing.
+            let value = AttributeValue::try_from(format!("it's a \"{value}\""));
+            prop_assert!(value.is_ok());
+
+            let block = Block::new(
+                BlockName::try_from("template").unwrap(),
+                vec![(AttributeName::try_from("title").unwrap(), value.ok())],
+                Cow::Borrowed(""),
+            );
+            prop_assert!(block.is_ok());
+
+            let block = block.unwrap();
+            let printed = block.to_string();
+
+            prop_assert_eq!(crate::parse(&printed).unwrap(), vec![Section::Block(block)]);
+        }
+
+        #[test]
+        fn test_round_trip_parsed(input in input()) {
+            if let Ok(sfc) = Sfc::parse(&input) {
+                let printed = sfc.to_string();
+
+                prop_assert_eq!(Sfc::parse(&printed).unwrap(), sfc);
+            }
+        }
+
+        #[test]
+        fn test_round_trip_built(
+            blocks in prop::collection::vec(block_parts(), 0..4),
+            raws in prop::collection::vec(text(MARKUP, 8), 0..4),
+        ) {
+            // Sections built with the validating constructors, raw sections between blocks.
+            let mut sfc = Sfc::new();
+
+            for ((name, attributes, content), raw) in blocks.into_iter().zip(raws) {
+                let Ok(block) = Block::new(name, attributes, Cow::Owned(content)) else {
+                    continue;
+                };
+
+                if let Ok(raw) = Raw::try_from(raw) {
+                    sfc.push(raw);
+                }
+
+                sfc.push(block);
+            }
+
+            let printed = sfc.to_string();
+
+            prop_assert_eq!(Sfc::parse(&printed).unwrap(), sfc);
+        }
+    }
+
+    #[test]
+    fn test_sfc_display() {
+        assert_eq!(Sfc::new().to_string(), "");
+        assert_eq!(
+            Sfc::parse("<!-- a --><template><p>a</p></template>\n\n\n<style></style>")
+                .unwrap()
+                .to_string(),
+            "<!-- a -->\n\n<template>\n<p>a</p>\n</template>\n\n<style></style>\n"
+        );
+    }
+
+    #[test]
+    fn test_sfc_reorder() {
+        let mut sfc =
+            Sfc::parse("<a></a>\n<!-- b -->\n<b></b>\n<c></c>\n<!-- trailing -->").unwrap();
+        let names = |sfc: &Sfc| {
+            sfc.iter()
+                .map(|section| match section {
+                    Section::Block(block) => block.name.to_string(),
+                    Section::Raw(raw) => raw.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        sfc.move_section(0, 3);
+        assert_eq!(
+            names(&sfc),
+            ["<!-- b -->", "b", "c", "a", "<!-- trailing -->"]
+        );
+
+        sfc.move_section(3, 0);
+        assert_eq!(
+            names(&sfc),
+            ["a", "<!-- b -->", "b", "c", "<!-- trailing -->"]
+        );
+
+        sfc.sort_blocks_by_key(|block| std::cmp::Reverse(block.name.to_string()));
+        assert_eq!(
+            names(&sfc),
+            ["c", "<!-- b -->", "b", "a", "<!-- trailing -->"]
+        );
+
+        let raw = sfc.remove(1);
+        sfc.insert(0, raw);
+        assert_eq!(sfc.raws().count(), 2);
+        assert_eq!(sfc.block("B").map(|block| block.name.as_str()), Some("b"));
+    }
+
+    #[test]
+    fn test_sfc_buf() {
+        let source = "<!-- a -->\n<script lang=\"ts\" LANG=\"js\">a</script>\n<style />";
+        let options = ParseOptions {
+            duplicate_attributes: DuplicateAttributes::Warn,
+            ..ParseOptions::default()
+        };
+
+        let sfc = SfcBuf::parse_with_options(source.to_owned(), options).unwrap();
+
+        let sections = sfc.sections().collect::<Vec<_>>();
+        let owned = sections.iter().map(Section::to_static).collect::<Vec<_>>();
+
+        assert_eq!(sections, owned);
+        assert_eq!(sections.len(), 3);
+        assert!(matches!(&sections[1], Section::Block(block) if block.attributes.len() == 1));
+
+        drop(sections);
+        assert_eq!(sfc.into_source(), source);
+    }
+
+    #[test]
+    fn test_into_owned() {
+        let owned: Vec<Section<'static>> = {
+            let source = String::from("<template><p>a</p></template>");
+
+            crate::parse(&source)
+                .unwrap()
+                .into_iter()
+                .map(Section::into_owned)
+                .collect()
+        };
+
+        assert_eq!(
+            owned,
+            crate::parse("<template><p>a</p></template>").unwrap()
+        );
+    }
+}
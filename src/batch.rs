@@ -0,0 +1,287 @@
+//! Parse many Vue SFCs at once.
+//!
+//! Files are parsed in parallel when the `rayon` feature is enabled, sequentially otherwise.
+//! Results are returned in input order either way.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::{
+//!     batch::{parse_batch, BatchInput},
+//!     parser::ParseOptions,
+//! };
+//!
+//! let batch = parse_batch(
+//!     vec![
+//!         BatchInput::source("A.vue", "<template></template>"),
+//!         BatchInput::source("B.vue", "</template>"),
+//!     ],
+//!     &ParseOptions::default(),
+//! );
+//!
+//! assert_eq!(batch.files.len(), 2);
+//! assert!(batch.files[0].result.is_ok());
+//! assert_eq!(batch.errors().count(), 1);
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    ast::SfcBuf,
+    parser::{Encoding, ParseOptions, Source},
+};
+
+pub use self::error::BatchError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::io;
+
+    use crate::parser::{DecodeError, ParseError};
+
+    /// The reason a file of a batch could not be parsed.
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum BatchError {
+        /// The file could not be read.
+        Io(io::Error),
+        /// The file is not valid UTF-8.
+        Decode(DecodeError),
+        /// The file could not be parsed, `text` is its decoded content, i.e: to locate the error.
+        Parse { text: String, error: ParseError },
+    }
+
+    impl Display for BatchError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Io(err) => write!(f, "unable to read file: {err}"),
+                Self::Decode(err) => err.fmt(f),
+                Self::Parse { error, .. } => error.fmt(f),
+            }
+        }
+    }
+
+    impl Error for BatchError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::Io(err) => Some(err),
+                Self::Decode(err) => Some(err),
+                Self::Parse { error, .. } => Some(error),
+            }
+        }
+    }
+
+    impl From<io::Error> for BatchError {
+        fn from(err: io::Error) -> Self {
+            Self::Io(err)
+        }
+    }
+
+    impl From<DecodeError> for BatchError {
+        fn from(err: DecodeError) -> Self {
+            Self::Decode(err)
+        }
+    }
+}
+
+/// A file to parse, see [`parse_batch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchInput {
+    /// Read from the file system, decoded as UTF-8 (a leading byte order mark is stripped).
+    Path(PathBuf),
+    /// An in-memory source, `path` only identifies it in the results.
+    Source { path: PathBuf, source: String },
+}
+
+impl BatchInput {
+    pub fn source(path: impl Into<PathBuf>, source: impl Into<String>) -> Self {
+        Self::Source {
+            path: path.into(),
+            source: source.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Path(path) | Self::Source { path, .. } => path,
+        }
+    }
+}
+
+impl From<PathBuf> for BatchInput {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&Path> for BatchInput {
+    fn from(path: &Path) -> Self {
+        Self::Path(path.to_owned())
+    }
+}
+
+/// The result of parsing a file of a batch.
+#[derive(Debug)]
+pub struct BatchFile {
+    pub path: PathBuf,
+    /// The parsed file, its warnings are available through [`SfcBuf::warnings`].
+    pub result: Result<SfcBuf, BatchError>,
+    /// Time spent reading & decoding the file, zero for in-memory sources.
+    pub read: Duration,
+    /// Time spent parsing the file.
+    pub parse: Duration,
+}
+
+/// Aggregate timings of a batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchTimings {
+    /// Elapsed time for the whole batch.
+    pub wall: Duration,
+    /// Time spent reading files, summed over all files.
+    pub read: Duration,
+    /// Time spent parsing files, summed over all files.
+    pub parse: Duration,
+}
+
+/// The result of [`parse_batch`].
+#[derive(Debug)]
+pub struct Batch {
+    /// One result per input, in input order.
+    pub files: Vec<BatchFile>,
+    pub timings: BatchTimings,
+}
+
+impl Batch {
+    /// Files which could not be parsed, along with their error.
+    pub fn errors(&self) -> impl Iterator<Item = (&Path, &BatchError)> {
+        self.files
+            .iter()
+            .filter_map(|file| Some((file.path.as_path(), file.result.as_ref().err()?)))
+    }
+
+    /// Whether every file was parsed successfully.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+/// Parse every input with the given options.
+///
+/// An input failing to be read or parsed does not stop the batch, its error is reported in the
+/// matching [`BatchFile`].
+#[must_use]
+pub fn parse_batch(inputs: Vec<BatchInput>, options: &ParseOptions) -> Batch {
+    let start = Instant::now();
+
+    #[cfg(feature = "rayon")]
+    let files: Vec<BatchFile> = inputs
+        .into_par_iter()
+        .map(|input| parse_one(input, options))
+        .collect();
+
+    #[cfg(not(feature = "rayon"))]
+    let files: Vec<BatchFile> = inputs
+        .into_iter()
+        .map(|input| parse_one(input, options))
+        .collect();
+
+    let timings = files.iter().fold(
+        BatchTimings {
+            wall: start.elapsed(),
+            ..BatchTimings::default()
+        },
+        |timings, file| BatchTimings {
+            read: timings.read + file.read,
+            parse: timings.parse + file.parse,
+            ..timings
+        },
+    );
+
+    Batch { files, timings }
+}
+
+fn parse_one(input: BatchInput, options: &ParseOptions) -> BatchFile {
+    let (path, source, read) = match input {
+        BatchInput::Path(path) => {
+            let start = Instant::now();
+            let source = read(&path);
+
+            (path, source, start.elapsed())
+        }
+        BatchInput::Source { path, source } => (path, Ok(source), Duration::ZERO),
+    };
+
+    let start = Instant::now();
+
+    let result = source.and_then(|source| {
+        SfcBuf::try_parse(source, options.clone())
+            .map_err(|(text, error)| BatchError::Parse { text, error })
+    });
+
+    BatchFile {
+        path,
+        result,
+        read,
+        parse: start.elapsed(),
+    }
+}
+
+fn read(path: &Path) -> Result<String, BatchError> {
+    let bytes = fs::read(path)?;
+
+    Ok(Source::decode(&bytes, Encoding::Utf8)?.into_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::parser::{DuplicateAttributes, ParseOptions};
+
+    use super::{parse_batch, BatchError, BatchInput};
+
+    #[test]
+    fn test_parse_batch() {
+        let options = ParseOptions {
+            duplicate_attributes: DuplicateAttributes::Warn,
+            ..ParseOptions::default()
+        };
+
+        let batch = parse_batch(
+            vec![
+                BatchInput::source("A.vue", "<script lang=\"ts\" lang=\"js\"></script>"),
+                BatchInput::from(std::path::Path::new("does/not/exist.vue")),
+                BatchInput::source("C.vue", "<template>"),
+            ],
+            &options,
+        );
+
+        let paths = batch
+            .files
+            .iter()
+            .map(|file| file.path.to_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, ["A.vue", "does/not/exist.vue", "C.vue"]);
+        assert!(!batch.is_ok());
+        assert_eq!(batch.files[0].result.as_ref().unwrap().warnings().len(), 1);
+        assert_eq!(batch.files[0].read, Duration::ZERO);
+        assert!(matches!(batch.files[1].result, Err(BatchError::Io(_))));
+        assert!(
+            matches!(&batch.files[2].result, Err(BatchError::Parse { text, .. }) if text == "<template>")
+        );
+        assert_eq!(
+            batch.timings.parse,
+            batch.files.iter().map(|file| file.parse).sum()
+        );
+    }
+}
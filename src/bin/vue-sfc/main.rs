@@ -0,0 +1,668 @@
+//! Command-line interface to `vue-sfc`.
+
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use vue_sfc::{
+    batch::{parse_batch, BatchError, BatchInput},
+    format::{format, BlockOrder, FormatOptions, Indent, LineEndings, QuoteStyle, RawPlacement},
+    graph::ComponentGraph,
+    lint::{apply_fixes, Linter, Severity},
+    parser::{DuplicateAttributes, Encoding, LineEnding, ParseError, ParseOptions, Source},
+    scan::{scan, walk, ScanOptions},
+    split::Manifest,
+    Section,
+};
+
+#[derive(Parser)]
+#[command(name = "vue-sfc", version, about = "Parse and interact with Vue SFC.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parse a file and print its sections.
+    Parse {
+        /// The file to parse, `-` for stdin.
+        file: PathBuf,
+        /// Print a `@vue/compiler-sfc` compatible descriptor as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the content of a block.
+    Extract {
+        /// The file to parse, `-` for stdin.
+        file: PathBuf,
+        /// Name of the block, i.e: `style`.
+        #[arg(long)]
+        block: String,
+        /// Index of the block among blocks with the same name.
+        #[arg(long, default_value_t = 0)]
+        index: usize,
+    },
+    /// Report parsing errors, directories are searched for `.vue` files.
+    Check {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+    },
+    /// Run lint rules, directories are searched for `.vue` files.
+    Lint {
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Apply automatic fixes in place.
+        #[arg(long)]
+        fix: bool,
+        /// Disable a rule, i.e: `--allow script-lang`.
+        #[arg(long, value_name = "RULE")]
+        allow: Vec<String>,
+        /// Report a rule as an error, i.e: `--deny style-scoped`.
+        #[arg(long, value_name = "RULE")]
+        deny: Vec<String>,
+    },
+    /// Format files in place, directories are searched for `.vue` files.
+    Fmt {
+        /// Files to format, `-` to format stdin to stdout.
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+        /// Do not write files, fail if any of them is not formatted.
+        #[arg(long)]
+        check: bool,
+        #[command(flatten)]
+        options: FmtArgs,
+    },
+    /// Split a file into one file per block, plus a `{stem}.manifest.json` manifest.
+    Split {
+        file: PathBuf,
+        /// Where to write files, defaults to the directory of `file`.
+        #[arg(long, short)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Join files described by a manifest back into a single file.
+    Join {
+        manifest: PathBuf,
+        /// Where to write the file, defaults to stdout.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Print an index of the components of a project as JSON.
+    Index {
+        root: PathBuf,
+        /// Only index files matching these globs, defaults to `**/*.vue`.
+        #[arg(long, value_name = "GLOB")]
+        include: Vec<String>,
+        /// Skip files & directories matching these globs, defaults to `**/node_modules`.
+        #[arg(long, value_name = "GLOB")]
+        exclude: Vec<String>,
+    },
+    /// Print the component usage graph of a project, reporting import cycles.
+    Graph {
+        root: PathBuf,
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+        /// Resolve imports starting with `PREFIX` from `PATH` (relative to `root`),
+        /// i.e: `--alias @/=src/`.
+        #[arg(long, value_name = "PREFIX=PATH")]
+        alias: Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    Dot,
+    /// Nodes, edges, import cycles & unused components.
+    Json,
+}
+
+#[derive(Args)]
+struct FmtArgs {
+    /// Order of blocks.
+    #[arg(long, value_enum, default_value_t = FmtOrder::Preserve)]
+    order: FmtOrder,
+    /// Number of blank lines between sections.
+    #[arg(long, default_value_t = 1)]
+    blank_lines: usize,
+    /// Attributes to print first, in order, i.e: `setup,lang`.
+    #[arg(long, value_delimiter = ',')]
+    attribute_order: Vec<String>,
+    /// Quotes used around attribute values.
+    #[arg(long, value_enum, default_value_t = FmtQuote::Double)]
+    quote: FmtQuote,
+    /// Re-indent block contents by this many spaces (only blocks listed in `--indent-blocks`).
+    #[arg(long)]
+    indent: Option<usize>,
+    /// Blocks indented by `--indent`, others are left untouched.
+    #[arg(long, value_delimiter = ',', default_value = "template")]
+    indent_blocks: Vec<String>,
+    /// Placement of raw sections (i.e: comments between blocks).
+    #[arg(long, value_enum, default_value_t = FmtRaw::Attach)]
+    raw: FmtRaw,
+    /// Line endings, `auto` uses the dominant line ending of each file.
+    #[arg(long, value_enum, default_value_t = FmtLineEnding::Auto)]
+    line_ending: FmtLineEnding,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FmtOrder {
+    Preserve,
+    ScriptFirst,
+    TemplateFirst,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FmtQuote {
+    Double,
+    Single,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FmtRaw {
+    Attach,
+    Top,
+    Remove,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FmtLineEnding {
+    Auto,
+    Lf,
+    Crlf,
+}
+
+impl From<FmtArgs> for FormatOptions {
+    fn from(args: FmtArgs) -> Self {
+        Self {
+            block_order: match args.order {
+                FmtOrder::Preserve => BlockOrder::Preserve,
+                FmtOrder::ScriptFirst => BlockOrder::ScriptFirst,
+                FmtOrder::TemplateFirst => BlockOrder::TemplateFirst,
+            },
+            blank_lines: args.blank_lines,
+            attribute_order: args.attribute_order,
+            quote_style: match args.quote {
+                FmtQuote::Double => QuoteStyle::Double,
+                FmtQuote::Single => QuoteStyle::Single,
+            },
+            indent: match args.indent {
+                Some(width) => Indent::Blocks {
+                    unit: " ".repeat(width),
+                    names: args.indent_blocks,
+                },
+                None => Indent::Preserve,
+            },
+            raw_placement: match args.raw {
+                FmtRaw::Attach => RawPlacement::Attach,
+                FmtRaw::Top => RawPlacement::Top,
+                FmtRaw::Remove => RawPlacement::Remove,
+            },
+            line_endings: match args.line_ending {
+                FmtLineEnding::Auto => LineEndings::Preserve,
+                FmtLineEnding::Lf => LineEndings::Normalize(LineEnding::Lf),
+                FmtLineEnding::Crlf => LineEndings::Normalize(LineEnding::CrLf),
+            },
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Parse { file, json } => parse(&file, json),
+        Command::Extract { file, block, index } => extract(&file, &block, index),
+        Command::Check { paths } => check(&paths),
+        Command::Lint {
+            paths,
+            fix,
+            allow,
+            deny,
+        } => lint(&paths, fix, &allow, &deny),
+        Command::Fmt {
+            paths,
+            check,
+            options,
+        } => fmt(&paths, check, &options.into()),
+        Command::Split { file, out_dir } => split(&file, out_dir.as_deref()),
+        Command::Join { manifest, output } => join(&manifest, output.as_deref()),
+        Command::Index {
+            root,
+            include,
+            exclude,
+        } => index(&root, include, exclude),
+        Command::Graph {
+            root,
+            format,
+            alias,
+        } => graph(&root, format, &alias),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse(file: &Path, json: bool) -> Result<ExitCode, Box<dyn Error>> {
+    let source = read(file)?;
+    let mut stdout = io::stdout().lock();
+
+    if json {
+        let filename = file.to_string_lossy();
+        let result = vue_sfc::descriptor::parse(&filename, &source)
+            .map_err(|err| located(file, &source, &err))?;
+
+        for err in &result.errors {
+            eprintln!("{}: warning: {err}", file.display());
+        }
+
+        serde_json::to_writer_pretty(&mut stdout, &result.descriptor)?;
+        writeln!(stdout)?;
+
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let sections = vue_sfc::parse(&source).map_err(|err| located(file, &source, &err))?;
+
+    for section in sections {
+        match section {
+            Section::Block(block) => {
+                write!(stdout, "block `{}`", block.name)?;
+
+                for (name, value) in &block.attributes {
+                    match value {
+                        Some(value) => write!(stdout, " {name}={:?}", value.as_str())?,
+                        None => write!(stdout, " {name}")?,
+                    }
+                }
+
+                writeln!(stdout, ", {} bytes", block.content.len())?;
+            }
+            Section::Raw(raw) => writeln!(stdout, "raw, {} bytes", raw.len())?,
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn extract(file: &Path, name: &str, index: usize) -> Result<ExitCode, Box<dyn Error>> {
+    let source = read(file)?;
+    let sections = vue_sfc::parse(&source).map_err(|err| located(file, &source, &err))?;
+
+    let block = sections
+        .iter()
+        .filter_map(|section| match section {
+            Section::Block(block) if block.name == name => Some(block),
+            _ => None,
+        })
+        .nth(index)
+        .ok_or_else(|| format!("{}: no block `{name}` at index {index}", file.display()))?;
+
+    writeln!(io::stdout().lock(), "{}", block.content)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn check(paths: &[PathBuf]) -> Result<ExitCode, Box<dyn Error>> {
+    let inputs = collect_files(paths)?
+        .into_iter()
+        .map(|file| {
+            Ok(if is_stdin(&file) {
+                BatchInput::Source {
+                    source: read(&file)?,
+                    path: file,
+                }
+            } else {
+                BatchInput::Path(file)
+            })
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+    let options = ParseOptions {
+        duplicate_attributes: DuplicateAttributes::Warn,
+        ..ParseOptions::default()
+    };
+
+    let batch = parse_batch(inputs, &options);
+    let mut failures = 0_usize;
+
+    for file in &batch.files {
+        match &file.result {
+            Ok(sfc) => {
+                for warning in sfc.warnings() {
+                    let (line, column) = line_column(sfc.source(), warning.offset());
+                    eprintln!(
+                        "{}:{line}:{column}: warning: {warning}",
+                        file.path.display()
+                    );
+                }
+            }
+            Err(BatchError::Parse { text, error }) => {
+                failures += 1;
+                eprintln!("{}", located(&file.path, text, error));
+            }
+            Err(err) => {
+                failures += 1;
+                eprintln!("{}: error: {err}", file.path.display());
+            }
+        }
+    }
+
+    eprintln!(
+        "checked {} file(s) in {:.2?}, {failures} error(s)",
+        batch.files.len(),
+        batch.timings.wall
+    );
+
+    Ok(if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn lint(
+    paths: &[PathBuf],
+    fix: bool,
+    allow: &[String],
+    deny: &[String],
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut linter = Linter::default();
+
+    for rule in allow {
+        linter.set_severity(rule, Severity::Off);
+    }
+
+    for rule in deny {
+        linter.set_severity(rule, Severity::Error);
+    }
+
+    let mut errors = 0_usize;
+
+    for file in collect_files(paths)? {
+        let source = match read(&file) {
+            Ok(source) => source,
+            Err(err) => {
+                errors += 1;
+                eprintln!("{}: error: {err}", file.display());
+                continue;
+            }
+        };
+
+        let diagnostics = match linter.lint(&source) {
+            Ok(diagnostics) => diagnostics,
+            Err(err) => {
+                errors += 1;
+                eprintln!("{}", located(&file, &source, &err));
+                continue;
+            }
+        };
+
+        for diagnostic in &diagnostics {
+            if diagnostic.severity == Severity::Error {
+                errors += 1;
+            }
+
+            let (line, column) = line_column(&source, diagnostic.span.start);
+            eprintln!("{}:{line}:{column}: {diagnostic}", file.display());
+        }
+
+        if fix && !is_stdin(&file) && diagnostics.iter().any(|d| !d.fix.is_empty()) {
+            fs::write(&file, apply_fixes(&source, &diagnostics))?;
+        }
+    }
+
+    Ok(if errors == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn fmt(
+    paths: &[PathBuf],
+    check: bool,
+    options: &FormatOptions,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut unformatted = 0_usize;
+
+    for file in collect_files(paths)? {
+        let source = read(&file)?;
+        let sections = vue_sfc::parse(&source).map_err(|err| located(&file, &source, &err))?;
+        let mut formatted = match options.line_endings {
+            LineEndings::Preserve => format(
+                &sections,
+                &FormatOptions {
+                    line_endings: LineEndings::Normalize(LineEnding::detect([source.as_str()])),
+                    ..options.clone()
+                },
+            ),
+            LineEndings::Normalize(_) => format(&sections, options),
+        };
+
+        if source.starts_with('\u{FEFF}') {
+            formatted.insert(0, '\u{FEFF}');
+        }
+
+        if is_stdin(&file) {
+            io::stdout().lock().write_all(formatted.as_bytes())?;
+        } else if formatted != source {
+            if check {
+                unformatted += 1;
+                eprintln!("{}: not formatted", file.display());
+            } else {
+                fs::write(&file, formatted)?;
+            }
+        }
+    }
+
+    Ok(if unformatted == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn split(file: &Path, out_dir: Option<&Path>) -> Result<ExitCode, Box<dyn Error>> {
+    let source = read(file)?;
+    let sections = vue_sfc::parse(&source).map_err(|err| located(file, &source, &err))?;
+
+    let stem = file
+        .file_stem()
+        .ok_or_else(|| format!("{}: not a file", file.display()))?
+        .to_string_lossy();
+    let out_dir = out_dir.unwrap_or_else(|| file.parent().unwrap_or_else(|| Path::new("")));
+
+    let split = vue_sfc::split::split(&stem, &sections);
+
+    for split_file in &split.files {
+        let path = out_dir.join(&split_file.name);
+        fs::write(&path, &split_file.content)?;
+        println!("{}", path.display());
+    }
+
+    let path = out_dir.join(format!("{stem}.manifest.json"));
+    fs::write(&path, serde_json::to_string_pretty(&split.manifest)? + "\n")?;
+    println!("{}", path.display());
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn join(manifest: &Path, output: Option<&Path>) -> Result<ExitCode, Box<dyn Error>> {
+    let dir = manifest.parent().unwrap_or_else(|| Path::new(""));
+    let manifest: Manifest = serde_json::from_str(&read(manifest)?)?;
+
+    let sections = vue_sfc::split::join(&manifest, |name| {
+        // Block files must be next to the manifest, i.e: not `../secret` nor `/etc/passwd`.
+        let path = Path::new(name);
+
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "file must be inside the manifest directory",
+            ));
+        }
+
+        fs::read_to_string(dir.join(path))
+    })?;
+    let joined = format(&sections, &FormatOptions::default());
+
+    match output {
+        Some(output) => fs::write(output, joined)?,
+        None => io::stdout().lock().write_all(joined.as_bytes())?,
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn index(
+    root: &Path,
+    include: Vec<String>,
+    exclude: Vec<String>,
+) -> Result<ExitCode, Box<dyn Error>> {
+    let mut options = ScanOptions::default();
+
+    if !include.is_empty() {
+        options.include = include;
+    }
+
+    if !exclude.is_empty() {
+        options.exclude = exclude;
+    }
+
+    let index = scan(root, &options)?;
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &index)?;
+    writeln!(stdout)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn graph(root: &Path, format: GraphFormat, aliases: &[String]) -> Result<ExitCode, Box<dyn Error>> {
+    let mut builder = ComponentGraph::builder();
+
+    for alias in aliases {
+        let (prefix, path) = alias
+            .split_once('=')
+            .ok_or_else(|| format!("invalid alias `{alias}`, expected `PREFIX=PATH`"))?;
+
+        builder.alias(prefix, path);
+    }
+
+    let index = builder.add_project(root, &ScanOptions::default())?;
+
+    for err in &index.errors {
+        eprintln!("warning: {}", err.error);
+    }
+
+    for component in index.failed() {
+        eprintln!("{}: skipped, unable to parse", component.path.display());
+    }
+
+    let graph = builder.build();
+    let cycles = graph.cycles();
+
+    for cycle in &cycles {
+        let names = cycle
+            .iter()
+            .map(|&node| graph.nodes[node].path.display().to_string())
+            .collect::<Vec<_>>();
+
+        eprintln!("warning: import cycle: {}", names.join(", "));
+    }
+
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        GraphFormat::Dot => write!(stdout, "{}", graph.to_dot())?,
+        GraphFormat::Json => {
+            let unused = graph.unused().collect::<Vec<_>>();
+
+            serde_json::to_writer_pretty(
+                &mut stdout,
+                &serde_json::json!({
+                    "nodes": graph.nodes,
+                    "edges": graph.edges,
+                    "cycles": cycles,
+                    "unused": unused,
+                }),
+            )?;
+            writeln!(stdout)?;
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    if is_stdin(path) {
+        let mut buffer = String::new();
+        io::stdin().lock().read_to_string(&mut buffer)?;
+        Ok(buffer)
+    } else {
+        let bytes = fs::read(path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let source = Source::decode(&bytes, Encoding::Utf8)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+
+        // Keep the byte order mark, so that `fmt` preserves it.
+        Ok(if source.has_bom() {
+            format!("\u{FEFF}{}", source.as_str())
+        } else {
+            source.into_string()
+        })
+    }
+}
+
+/// Expand directories into the `.vue` files they contain, see [`walk`].
+fn collect_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            let (paths, errors) = walk(path, &ScanOptions::default())?;
+
+            for err in &errors {
+                eprintln!("warning: {}", err.error);
+            }
+
+            files.extend(paths.into_iter().map(|file| path.join(file)));
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Format a parse error as `file:line:column: error: message`.
+fn located(file: &Path, source: &str, err: &ParseError) -> String {
+    let (line, column) = line_column(source, err.offset());
+
+    format!("{}:{line}:{column}: error: {err}", file.display())
+}
+
+/// 1-based line & column (in characters) of `offset`.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&ch| ch != '\n').count() + 1;
+
+    (line, column)
+}
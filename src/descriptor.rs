@@ -0,0 +1,625 @@
+//! A representation of a Vue SFC mirroring the `SFCDescriptor` of [`@vue/compiler-sfc`][1].
+//!
+//! With the `serde` feature enabled, [`Descriptor`] serializes to the same JSON shape as the
+//! descriptor returned by `@vue/compiler-sfc`'s `parse`.
+//!
+//! [1]: https://github.com/vuejs/core/tree/main/packages/compiler-sfc
+
+use std::fmt::Display;
+
+use crate::{
+    parser::{parse_with_spans, ParseError, SectionSpan},
+    AttributeValue, Block, Section,
+};
+
+pub use self::error::DescriptorError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    /// A non-fatal error encountered while building a [`Descriptor`][super::Descriptor].
+    ///
+    /// The offending block is left out of the descriptor.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum DescriptorError {
+        /// More than one `<template>` block.
+        DuplicateTemplate,
+        /// More than one `<script>` block without a `setup` attribute.
+        DuplicateScript,
+        /// More than one `<script setup>` block.
+        DuplicateScriptSetup,
+    }
+
+    impl Display for DescriptorError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::DuplicateTemplate => write!(
+                    f,
+                    "single file component can contain only one `<template>` element"
+                ),
+                Self::DuplicateScript => write!(
+                    f,
+                    "single file component can contain only one `<script>` element"
+                ),
+                Self::DuplicateScriptSetup => write!(
+                    f,
+                    "single file component can contain only one `<script setup>` element"
+                ),
+            }
+        }
+    }
+
+    impl Error for DescriptorError {}
+
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for DescriptorError {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+}
+
+/// The result of [`parse`], mirroring `SFCParseResult`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DescriptorParseResult<'a> {
+    pub descriptor: Descriptor<'a>,
+    pub errors: Vec<DescriptorError>,
+}
+
+/// Mirrors `SFCDescriptor`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Descriptor<'a> {
+    pub filename: &'a str,
+    pub source: &'a str,
+    pub template: Option<DescriptorBlock<'a>>,
+    pub script: Option<DescriptorBlock<'a>>,
+    pub script_setup: Option<DescriptorBlock<'a>>,
+    pub styles: Vec<DescriptorBlock<'a>>,
+    pub custom_blocks: Vec<DescriptorBlock<'a>>,
+    /// Expressions used in `v-bind()` in styles.
+    pub css_vars: Vec<&'a str>,
+    /// Whether any scoped style uses the `:slotted()` pseudo-class.
+    pub slotted: bool,
+}
+
+/// Mirrors `SFCBlock` and its `SFCTemplateBlock`, `SFCScriptBlock` & `SFCStyleBlock` variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DescriptorBlock<'a> {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: String,
+    pub content: &'a str,
+    pub attrs: Attrs,
+    pub loc: SourceLocation<'a>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub lang: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub src: Option<String>,
+    /// Only set on `<script>` blocks.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub setup: Option<AttrValue>,
+    /// Only set on `<style>` blocks.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub scoped: Option<bool>,
+    /// Only set on `<style>` blocks.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub module: Option<AttrValue>,
+}
+
+/// Block attributes, serialized as an object preserving the source order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Attrs(pub Vec<(String, AttrValue)>);
+
+impl Attrs {
+    /// Returns the value of the first attribute named `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&AttrValue> {
+        self.0
+            .iter()
+            .find_map(|(key, value)| (key == name).then_some(value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Attrs {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        }
+
+        map.end()
+    }
+}
+
+/// An attribute value, either a string or `true` when the attribute has no (or an empty) value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum AttrValue {
+    String(String),
+    True(True),
+}
+
+/// A unit type serialized as `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct True;
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for True {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(true)
+    }
+}
+
+impl AttrValue {
+    fn new(value: Option<&AttributeValue>) -> Self {
+        match value.map(AttributeValue::as_str) {
+            Some(value) if !value.is_empty() => Self::String(value.to_owned()),
+            _ => Self::True(True),
+        }
+    }
+
+    /// Returns the value as a string, if any.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            Self::True(_) => None,
+        }
+    }
+}
+
+/// Mirrors `SourceLocation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SourceLocation<'a> {
+    pub source: &'a str,
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Mirrors `Position`.
+///
+/// To match JavaScript string indexing, `offset` and `column` are counted in UTF-16 code units.
+/// `line` and `column` are 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Compute the position of the byte `offset` in `source`.
+    ///
+    /// # Panics
+    /// Will panic if `offset` is not on a char boundary of `source`.
+    #[must_use]
+    pub fn new(source: &str, offset: usize) -> Self {
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        Self {
+            offset: before.encode_utf16().count(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].encode_utf16().count() + 1,
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Parse the given input and build a [`Descriptor`] from it.
+///
+/// Raw sections are ignored, like `@vue/compiler-sfc` ignores root-level text and comments.
+/// Like `@vue/compiler-sfc`, block `content` & `loc` span everything between the start and end
+/// tags, unlike the content returned by [`crate::parse`] which is trimmed of leading newlines and
+/// trailing whitespace. Blocks other than `<template>` with only whitespace content and no `src`
+/// attribute are ignored, as with the default `ignoreEmpty` option of `@vue/compiler-sfc`.
+///
+/// # Errors
+/// Will return an error if parsing fails.
+///
+/// # Example
+/// ```rust
+/// let source = "<script setup>\nconst a = 1\n</script>";
+/// let result = vue_sfc::descriptor::parse("App.vue", source).unwrap();
+///
+/// assert!(result.descriptor.script_setup.is_some());
+/// assert!(result.errors.is_empty());
+/// ```
+pub fn parse<'a>(
+    filename: &'a str,
+    source: &'a str,
+) -> Result<DescriptorParseResult<'a>, ParseError> {
+    let sections = parse_with_spans(source)?;
+
+    let mut descriptor = Descriptor {
+        filename,
+        source,
+        template: None,
+        script: None,
+        script_setup: None,
+        styles: Vec::new(),
+        custom_blocks: Vec::new(),
+        css_vars: Vec::new(),
+        slotted: false,
+    };
+    let mut errors = Vec::new();
+
+    for (section, span) in &sections {
+        let block = match section {
+            Section::Block(block) => block,
+            Section::Raw(_) => continue,
+        };
+
+        let name = &block.name;
+        let block = create_block(source, block, span);
+
+        if *name != "template" && block.src.is_none() && block.content.trim().is_empty() {
+            continue;
+        }
+
+        if *name == "template" {
+            if descriptor.template.is_some() {
+                errors.push(DescriptorError::DuplicateTemplate);
+            } else {
+                descriptor.template = Some(block);
+            }
+        } else if *name == "script" {
+            match (&block.setup, &descriptor.script, &descriptor.script_setup) {
+                (Some(_), _, Some(_)) => errors.push(DescriptorError::DuplicateScriptSetup),
+                (Some(_), _, None) => descriptor.script_setup = Some(block),
+                (None, Some(_), _) => errors.push(DescriptorError::DuplicateScript),
+                (None, None, _) => descriptor.script = Some(block),
+            }
+        } else if *name == "style" {
+            descriptor.styles.push(block);
+        } else {
+            descriptor.custom_blocks.push(block);
+        }
+    }
+
+    descriptor.css_vars = parse_css_vars(&descriptor.styles);
+    descriptor.slotted = descriptor.styles.iter().any(|style| {
+        style.scoped == Some(true)
+            && (style.content.contains(":slotted(") || style.content.contains("::v-slotted("))
+    });
+
+    Ok(DescriptorParseResult { descriptor, errors })
+}
+
+fn create_block<'a>(source: &'a str, block: &Block, span: &SectionSpan) -> DescriptorBlock<'a> {
+    let kind = &block.name;
+    // The end tag starts at the last `<` of the block, self-closing blocks have no content.
+    let start = span.start_tag.end;
+    let end = source[start..span.outer.end]
+        .rfind('<')
+        .map_or(start, |index| start + index);
+    let content = &source[start..end];
+
+    let mut descriptor_block = DescriptorBlock {
+        kind: kind.as_str().to_owned(),
+        content,
+        attrs: Attrs::default(),
+        loc: SourceLocation {
+            source: content,
+            start: Position::new(source, start),
+            end: Position::new(source, end),
+        },
+        lang: None,
+        src: None,
+        setup: None,
+        scoped: None,
+        module: None,
+    };
+
+    for (name, value) in &block.attributes {
+        let value = AttrValue::new(value.as_ref());
+
+        if *name == "lang" {
+            descriptor_block.lang = value.as_str().map(str::to_owned);
+        } else if *name == "src" {
+            descriptor_block.src = value.as_str().map(str::to_owned);
+        } else if *kind == "style" && *name == "scoped" {
+            descriptor_block.scoped = Some(true);
+        } else if *kind == "style" && *name == "module" {
+            descriptor_block.module = Some(value.clone());
+        } else if *kind == "script" && *name == "setup" {
+            descriptor_block.setup = Some(value.clone());
+        }
+
+        descriptor_block
+            .attrs
+            .0
+            .push((name.as_str().to_owned(), value));
+    }
+
+    descriptor_block
+}
+
+/// Collect the expressions passed to `v-bind()` in styles, ignoring comments.
+fn parse_css_vars<'a>(styles: &[DescriptorBlock<'a>]) -> Vec<&'a str> {
+    let mut vars = Vec::new();
+
+    for style in styles {
+        let content = style.content;
+        let mut index = 0;
+
+        while let Some(found) = find_v_bind(content, index) {
+            match found {
+                (Some(start), next) => {
+                    index = next;
+
+                    if let Some(end) = lex_binding(content, start) {
+                        let variable = normalize_expression(&content[start..end]);
+
+                        if !vars.contains(&variable) {
+                            vars.push(variable);
+                        }
+                    }
+                }
+                (None, next) => index = next,
+            }
+        }
+    }
+
+    vars
+}
+
+/// Find the next `v-bind(` at or after `from`, skipping comments.
+///
+/// Returns the offset right after the opening parenthesis (if a `v-bind(` was found)
+/// and the offset to resume searching from.
+fn find_v_bind(content: &str, from: usize) -> Option<(Option<usize>, usize)> {
+    let rest = &content[from..];
+    let index = rest.find(['/', 'v'])?;
+    let at = from + index;
+    let rest = &content[at..];
+
+    if let Some(comment) = rest.strip_prefix("/*") {
+        let end = comment
+            .find("*/")
+            .map_or(content.len(), |end| at + 2 + end + 2);
+        return Some((None, end));
+    }
+
+    if rest.starts_with("//") {
+        let end = rest.find('\n').map_or(content.len(), |end| at + end);
+        return Some((None, end));
+    }
+
+    if let Some(after) = rest.strip_prefix("v-bind") {
+        let trimmed = after.trim_start();
+
+        if trimmed.starts_with('(') {
+            let start = content.len() - trimmed.len() + 1;
+            return Some((Some(start), start));
+        }
+    }
+
+    Some((None, at + 1))
+}
+
+/// Find the offset of the parenthesis closing the binding starting at `start`.
+fn lex_binding(content: &str, start: usize) -> Option<usize> {
+    let mut quote = None;
+    let mut depth = 0_usize;
+
+    for (index, ch) in content[start..].char_indices() {
+        match (quote, ch) {
+            (Some(q), ch) if ch == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(start + index),
+            (None, ')') => depth -= 1,
+            (None, _) => {}
+        }
+    }
+
+    None
+}
+
+fn normalize_expression(expression: &str) -> &str {
+    let expression = expression.trim();
+
+    for quote in ['\'', '"'] {
+        if let Some(unquoted) = expression
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return unquoted;
+        }
+    }
+
+    expression
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, AttrValue, DescriptorError, Position, True};
+
+    #[test]
+    fn test_position() {
+        let source = "ab\ncd\u{1F600}e";
+
+        assert_eq!(
+            Position::new(source, 0),
+            Position {
+                offset: 0,
+                line: 1,
+                column: 1
+            }
+        );
+        assert_eq!(
+            Position::new(source, 4),
+            Position {
+                offset: 4,
+                line: 2,
+                column: 2
+            }
+        );
+        assert_eq!(
+            Position::new(source, source.len()),
+            Position {
+                offset: 8,
+                line: 2,
+                column: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let source = concat!(
+            "<template><div/></template>\n",
+            "<script>export default {}</script>\n",
+            "<script setup lang=\"ts\">\nconst a = 1\n</script>\n",
+            "<style scoped module=\"classes\">\n:slotted(div) { color: v-bind('theme.color') }\n",
+            "/* v-bind(ignored) */\n",
+            "</style>\n",
+            "<i18n locale=\"en\">{}</i18n>",
+        );
+
+        let result = parse("App.vue", source).unwrap();
+        let descriptor = result.descriptor;
+
+        assert!(result.errors.is_empty());
+
+        let template = descriptor.template.unwrap();
+        assert_eq!(template.content, "<div/>");
+        assert_eq!(template.loc.start.offset, 10);
+        assert_eq!(template.loc.end.offset, 16);
+
+        assert_eq!(descriptor.script.unwrap().content, "export default {}");
+
+        let script_setup = descriptor.script_setup.unwrap();
+        assert_eq!(script_setup.setup, Some(AttrValue::True(True)));
+        assert_eq!(script_setup.lang.as_deref(), Some("ts"));
+        assert_eq!(script_setup.content, "\nconst a = 1\n");
+        assert_eq!(script_setup.loc.source, script_setup.content);
+        assert_eq!(script_setup.loc.start.line, 3);
+        assert_eq!(script_setup.loc.start.column, 25);
+        assert_eq!(script_setup.loc.end.line, 5);
+        assert_eq!(script_setup.loc.end.column, 1);
+
+        assert_eq!(descriptor.styles.len(), 1);
+        assert_eq!(descriptor.styles[0].scoped, Some(true));
+        assert_eq!(
+            descriptor.styles[0].module,
+            Some(AttrValue::String("classes".to_owned()))
+        );
+        assert_eq!(descriptor.css_vars, vec!["theme.color"]);
+        assert!(descriptor.slotted);
+
+        assert_eq!(descriptor.custom_blocks.len(), 1);
+        assert_eq!(
+            descriptor.custom_blocks[0].attrs.get("locale"),
+            Some(&AttrValue::String("en".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_parse_case_insensitive() {
+        let source = concat!(
+            "<Template><div/></Template>",
+            "<Script SETUP LANG=\"ts\">a</Script>",
+            "<STYLE Scoped>b</STYLE>",
+        );
+        let result = parse("App.vue", source).unwrap();
+        let descriptor = result.descriptor;
+
+        assert!(result.errors.is_empty());
+        assert!(descriptor.template.is_some());
+        assert!(descriptor.script.is_none());
+
+        let script_setup = descriptor.script_setup.unwrap();
+        assert_eq!(script_setup.setup, Some(AttrValue::True(True)));
+        assert_eq!(script_setup.lang.as_deref(), Some("ts"));
+
+        assert_eq!(descriptor.styles.len(), 1);
+        assert_eq!(descriptor.styles[0].scoped, Some(true));
+        assert!(descriptor.custom_blocks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_ignore_empty() {
+        let source = concat!(
+            "<template></template>",
+            "<script>\n</script>",
+            "<style> </style>",
+            "<style src=\"./a.css\"></style>",
+        );
+        let result = parse("App.vue", source).unwrap();
+        let descriptor = result.descriptor;
+
+        assert!(descriptor.template.is_some());
+        assert!(descriptor.script.is_none());
+        assert_eq!(descriptor.styles.len(), 1);
+        assert_eq!(descriptor.styles[0].src.as_deref(), Some("./a.css"));
+    }
+
+    #[test]
+    fn test_parse_duplicates() {
+        let result = parse(
+            "App.vue",
+            "<template></template><template></template><script>a</script><script>b</script>",
+        )
+        .unwrap();
+
+        assert_eq!(
+            result.errors,
+            vec![
+                DescriptorError::DuplicateTemplate,
+                DescriptorError::DuplicateScript
+            ]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        let result = parse("App.vue", "<style scoped lang=\"scss\">a {}</style>").unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&result.descriptor).unwrap(),
+            serde_json::json!({
+                "filename": "App.vue",
+                "source": "<style scoped lang=\"scss\">a {}</style>",
+                "template": null,
+                "script": null,
+                "scriptSetup": null,
+                "styles": [{
+                    "type": "style",
+                    "content": "a {}",
+                    "attrs": { "scoped": true, "lang": "scss" },
+                    "loc": {
+                        "source": "a {}",
+                        "start": { "offset": 26, "line": 1, "column": 27 },
+                        "end": { "offset": 30, "line": 1, "column": 31 }
+                    },
+                    "lang": "scss",
+                    "scoped": true
+                }],
+                "customBlocks": [],
+                "cssVars": [],
+                "slotted": false
+            })
+        );
+    }
+}
@@ -1,12 +1,16 @@
 use std::fmt::Display;
 
-use crate::ast::{InvalidAttributeName, InvalidAttributeValue, InvalidBlockName, InvalidRaw};
-use crate::parser::ParseError;
+use crate::ast::{
+    InvalidAttributeName, InvalidAttributeValue, InvalidBlock, InvalidBlockName, InvalidRaw,
+};
+use crate::parser::{DecodeError, ParseError};
 
 #[derive(Debug)]
 enum ErrorKind {
     Parse(ParseError),
+    Decode(DecodeError),
     InvalidBlockName(InvalidBlockName),
+    InvalidBlock(InvalidBlock),
     InvalidAttributeName(InvalidAttributeName),
     InvalidAttributeValue(InvalidAttributeValue),
     InvalidRaw(InvalidRaw),
@@ -25,7 +29,9 @@ impl Error {
 
         match inner {
             ErrorKind::Parse(err) => err,
+            ErrorKind::Decode(err) => err,
             ErrorKind::InvalidBlockName(err) => err,
+            ErrorKind::InvalidBlock(err) => err,
             ErrorKind::InvalidAttributeName(err) => err,
             ErrorKind::InvalidAttributeValue(err) => err,
             ErrorKind::InvalidRaw(err) => err,
@@ -51,12 +57,24 @@ impl From<ParseError> for Error {
     }
 }
 
+impl From<DecodeError> for Error {
+    fn from(err: DecodeError) -> Self {
+        Self(ErrorKind::Decode(err))
+    }
+}
+
 impl From<InvalidBlockName> for Error {
     fn from(err: InvalidBlockName) -> Self {
         Self(ErrorKind::InvalidBlockName(err))
     }
 }
 
+impl From<InvalidBlock> for Error {
+    fn from(err: InvalidBlock) -> Self {
+        Self(ErrorKind::InvalidBlock(err))
+    }
+}
+
 impl From<InvalidAttributeName> for Error {
     fn from(err: InvalidAttributeName) -> Self {
         Self(ErrorKind::InvalidAttributeName(err))
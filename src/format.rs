@@ -0,0 +1,687 @@
+//! Configurable printing of a whole Vue SFC.
+//!
+//! Unlike [`Block`]'s [`Display`] implementation, which emits a fixed layout, [`format()`] lets
+//! callers choose the order of blocks, the spacing between sections, the order and quoting of
+//! attributes and the indentation of block contents.
+//!
+//! Block contents are left byte-identical, except for line endings (see [`LineEndings`]),
+//! re-indentation when requested with [`Indent::Blocks`], or when an [`EmbeddedFormatter`] is
+//! registered on a [`Formatter`].
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::format::{format, BlockOrder, FormatOptions};
+//!
+//! let sections = vue_sfc::parse("<template></template><script lang=\"ts\" setup></script>").unwrap();
+//!
+//! let options = FormatOptions {
+//!     block_order: BlockOrder::ScriptFirst,
+//!     attribute_order: vec![String::from("setup")],
+//!     ..FormatOptions::default()
+//! };
+//!
+//! assert_eq!(
+//!     format(&sections, &options),
+//!     "<script setup lang=\"ts\"></script>\n\n<template></template>\n"
+//! );
+//! ```
+
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{Display, Write},
+};
+
+use crate::{parser::LineEnding, Attribute, AttributeValue, Block, Section};
+
+/// Options for [`format()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Order of blocks, defaults to [`BlockOrder::Preserve`].
+    pub block_order: BlockOrder,
+    /// Number of blank lines between sections, defaults to `1`.
+    pub blank_lines: usize,
+    /// Attributes listed here are printed first, in this order.
+    /// Other attributes keep their relative order. Defaults to an empty list.
+    pub attribute_order: Vec<String>,
+    /// Quotes used around attribute values, defaults to [`QuoteStyle::Double`].
+    pub quote_style: QuoteStyle,
+    /// Indentation of block contents, defaults to [`Indent::Preserve`].
+    pub indent: Indent,
+    /// Placement of raw sections, defaults to [`RawPlacement::Attach`].
+    pub raw_placement: RawPlacement,
+    /// Line endings of the output, defaults to [`LineEndings::Preserve`].
+    pub line_endings: LineEndings,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            block_order: BlockOrder::default(),
+            blank_lines: 1,
+            attribute_order: Vec::new(),
+            quote_style: QuoteStyle::default(),
+            indent: Indent::default(),
+            raw_placement: RawPlacement::default(),
+            line_endings: LineEndings::default(),
+        }
+    }
+}
+
+/// See [`FormatOptions::block_order`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BlockOrder {
+    /// Keep blocks in source order.
+    #[default]
+    Preserve,
+    /// `script`, `template`, `style`, then custom blocks.
+    ScriptFirst,
+    /// `template`, `script`, `style`, then custom blocks.
+    TemplateFirst,
+}
+
+impl BlockOrder {
+    pub(crate) fn rank(self, block: &Block) -> usize {
+        let (first, second) = match self {
+            Self::Preserve => return 0,
+            Self::ScriptFirst => ("script", "template"),
+            Self::TemplateFirst => ("template", "script"),
+        };
+
+        if block.name == first {
+            0
+        } else if block.name == second {
+            1
+        } else if block.name == "style" {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+/// See [`FormatOptions::quote_style`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum QuoteStyle {
+    /// `U+0022 QUOTATION MARK (")`.
+    #[default]
+    Double,
+    /// `U+0027 APOSTROPHE (')`.
+    Single,
+}
+
+impl QuoteStyle {
+    fn char(self) -> char {
+        match self {
+            Self::Double => '\u{0022}',
+            Self::Single => '\u{0027}',
+        }
+    }
+}
+
+/// See [`FormatOptions::line_endings`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LineEndings {
+    /// Use the dominant line ending of block contents & raw sections (see [`LineEnding::detect`])
+    /// for every line.
+    ///
+    /// Line breaks between sections are not part of the sections, use
+    /// [`Normalize`][LineEndings::Normalize] with
+    /// [`Parsed::line_ending`][crate::parser::Parsed::line_ending] to take them into account.
+    #[default]
+    Preserve,
+    /// Use the given line ending for every line.
+    Normalize(LineEnding),
+}
+
+/// See [`FormatOptions::indent`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Indent {
+    /// Leave block contents untouched.
+    #[default]
+    Preserve,
+    /// Remove the common indentation of the contents of the blocks named in `names`, then
+    /// indent them by `unit`. Other blocks are left untouched, as with [`Indent::Preserve`].
+    Blocks { unit: String, names: Vec<String> },
+}
+
+/// See [`FormatOptions::raw_placement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RawPlacement {
+    /// Raw sections move along with the block following them,
+    /// trailing raw sections stay at the end.
+    #[default]
+    Attach,
+    /// Raw sections are moved before all blocks.
+    Top,
+    /// Raw sections are removed.
+    Remove,
+}
+
+/// Print sections according to `options`, ending with a newline unless there are no sections.
+///
+/// Use a [`Formatter`] to also format block contents.
+#[must_use]
+pub fn format(sections: &[Section], options: &FormatOptions) -> String {
+    format_with(sections, options, &[]).output
+}
+
+/// Formats the content of blocks written in an embedded language, see [`Formatter::register`].
+pub trait EmbeddedFormatter {
+    /// Format the content of `block`.
+    ///
+    /// `content` is the content of `block` with its common indentation removed,
+    /// the returned content is re-indented by the [`Formatter`].
+    ///
+    /// # Errors
+    /// Returned errors are reported in [`Formatted::errors`], the content of the block is then
+    /// left as is.
+    fn format(&self, content: &str, block: &Block) -> Result<String, Box<dyn Error + Send + Sync>>;
+}
+
+impl<F> EmbeddedFormatter for F
+where
+    F: Fn(&str, &Block) -> Result<String, Box<dyn Error + Send + Sync>>,
+{
+    fn format(&self, content: &str, block: &Block) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self(content, block)
+    }
+}
+
+struct Hook<'f> {
+    name: String,
+    lang: Option<String>,
+    formatter: Box<dyn EmbeddedFormatter + 'f>,
+}
+
+/// Formats a Vue SFC, delegating block contents to registered [`EmbeddedFormatter`]s.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::{format::{FormatOptions, Formatter}, Block};
+///
+/// let sections = vue_sfc::parse("<style lang=\"scss\">\n  a{}\n</style>").unwrap();
+///
+/// let mut formatter = Formatter::new(FormatOptions::default());
+/// formatter.register("style", Some("scss"), |content: &str, _: &Block| {
+///     Ok(content.replace("a{}", "a {}"))
+/// });
+///
+/// let formatted = formatter.format(&sections);
+///
+/// assert_eq!(formatted.output, "<style lang=\"scss\">\n  a {}\n</style>\n");
+/// assert!(formatted.errors.is_empty());
+/// ```
+pub struct Formatter<'f> {
+    options: FormatOptions,
+    hooks: Vec<Hook<'f>>,
+}
+
+impl<'f> Formatter<'f> {
+    #[must_use]
+    pub fn new(options: FormatOptions) -> Self {
+        Self {
+            options,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// Register a formatter for blocks named `name` whose `lang` attribute is `lang`
+    /// (`None` matches blocks without a `lang` attribute).
+    ///
+    /// Formatters registered later take precedence.
+    pub fn register(
+        &mut self,
+        name: &str,
+        lang: Option<&str>,
+        formatter: impl EmbeddedFormatter + 'f,
+    ) -> &mut Self {
+        self.hooks.insert(
+            0,
+            Hook {
+                name: name.to_owned(),
+                lang: lang.map(str::to_owned),
+                formatter: Box::new(formatter),
+            },
+        );
+
+        self
+    }
+
+    #[must_use]
+    pub fn options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// Print sections, formatting block contents with the registered formatters.
+    ///
+    /// A block whose formatter fails is printed with its original content.
+    #[must_use]
+    pub fn format(&self, sections: &[Section]) -> Formatted {
+        format_with(sections, &self.options, &self.hooks)
+    }
+}
+
+impl std::fmt::Debug for Formatter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Formatter")
+            .field("options", &self.options)
+            .field(
+                "hooks",
+                &self
+                    .hooks
+                    .iter()
+                    .map(|hook| (&hook.name, &hook.lang))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// The result of [`Formatter::format`].
+#[derive(Debug)]
+pub struct Formatted {
+    pub output: String,
+    /// Failures of embedded formatters.
+    pub errors: Vec<BlockFormatError>,
+}
+
+/// An [`EmbeddedFormatter`] failed to format a block.
+#[derive(Debug)]
+pub struct BlockFormatError {
+    /// Index of the block in the formatted sections.
+    pub index: usize,
+    pub name: String,
+    pub lang: Option<String>,
+    pub source: Box<dyn Error + Send + Sync>,
+}
+
+impl Display for BlockFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            index, name, lang, ..
+        } = self;
+
+        match lang {
+            Some(lang) => write!(f, "unable to format `{name}` block #{index} (`{lang}`)"),
+            None => write!(f, "unable to format `{name}` block #{index}"),
+        }
+    }
+}
+
+impl Error for BlockFormatError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+fn format_with(sections: &[Section], options: &FormatOptions, hooks: &[Hook]) -> Formatted {
+    let mut output = String::new();
+    let mut errors = Vec::new();
+
+    for (position, (index, section)) in order(sections, options).into_iter().enumerate() {
+        if position > 0 {
+            output.push_str(&"\n".repeat(options.blank_lines + 1));
+        }
+
+        match section {
+            Section::Raw(raw) => output.push_str(raw.as_str()),
+            Section::Block(block) => {
+                let lang = block.attribute_value("lang").map(AttributeValue::as_str);
+
+                let hook = hooks
+                    .iter()
+                    .find(|hook| block.name == hook.name.as_str() && hook.lang.as_deref() == lang);
+
+                let formatted = hook.and_then(|hook| {
+                    let mut dedented = String::new();
+                    reindent(&mut dedented, block.content.trim_end(), "");
+
+                    match hook.formatter.format(&dedented, block) {
+                        Ok(formatted) => Some(formatted),
+                        Err(source) => {
+                            errors.push(BlockFormatError {
+                                index,
+                                name: block.name.as_str().to_owned(),
+                                lang: lang.map(str::to_owned),
+                                source,
+                            });
+
+                            None
+                        }
+                    }
+                });
+
+                write_block(&mut output, block, formatted.as_deref(), options);
+            }
+        }
+    }
+
+    if !output.is_empty() {
+        output.push('\n');
+    }
+
+    let line_ending = match options.line_endings {
+        LineEndings::Preserve => LineEnding::detect(sections.iter().map(|section| match section {
+            Section::Block(block) => block.content.as_ref(),
+            Section::Raw(raw) => raw.as_str(),
+        })),
+        LineEndings::Normalize(line_ending) => line_ending,
+    };
+
+    if let Cow::Owned(normalized) = line_ending.normalize(&output) {
+        output = normalized;
+    }
+
+    Formatted { output, errors }
+}
+
+/// Order sections according to `options.block_order` and `options.raw_placement`.
+/// Returns sections along with their original index.
+fn order<'s, 'a>(
+    sections: &'s [Section<'a>],
+    options: &FormatOptions,
+) -> Vec<(usize, &'s Section<'a>)> {
+    // Group each block with the raw sections preceding it.
+    let mut groups = Vec::new();
+    let mut pending = Vec::new();
+
+    for (index, section) in sections.iter().enumerate() {
+        pending.push((index, section));
+
+        if let Section::Block(block) = section {
+            groups.push((
+                options.block_order.rank(block),
+                std::mem::take(&mut pending),
+            ));
+        }
+    }
+
+    if !pending.is_empty() {
+        groups.push((usize::MAX, pending));
+    }
+
+    groups.sort_by_key(|(rank, _)| *rank);
+
+    let sections = groups.into_iter().flat_map(|(_, sections)| sections);
+
+    match options.raw_placement {
+        RawPlacement::Attach => sections.collect(),
+        RawPlacement::Top => {
+            let (mut raws, blocks): (Vec<_>, Vec<_>) =
+                sections.partition(|(_, section)| matches!(section, Section::Raw(_)));
+            raws.extend(blocks);
+            raws
+        }
+        RawPlacement::Remove => sections
+            .filter(|(_, section)| matches!(section, Section::Block(_)))
+            .collect(),
+    }
+}
+
+/// Write `block`, with `formatted` as its (unindented) content if specified.
+fn write_block(
+    output: &mut String,
+    block: &Block,
+    formatted: Option<&str>,
+    options: &FormatOptions,
+) {
+    let name = &block.name;
+
+    let _ = write!(output, "<{name}");
+
+    for (name, value) in sort_attributes(&block.attributes, &options.attribute_order) {
+        match value {
+            Some(value) => {
+                let _ = write!(output, " {name}=");
+                let _ = value.write_quoted(output, options.quote_style.char());
+            }
+            None => {
+                let _ = write!(output, " {name}");
+            }
+        }
+    }
+
+    output.push('>');
+
+    let content = block.content.trim_end();
+    let formatted = formatted.map(str::trim_end);
+
+    if !formatted.unwrap_or(content).is_empty() {
+        output.push('\n');
+
+        match (&options.indent, formatted) {
+            (Indent::Blocks { unit, names }, formatted)
+                if names.iter().any(|other| *name == other.as_str()) =>
+            {
+                reindent(output, formatted.unwrap_or(content), unit);
+            }
+            (_, None) => output.push_str(content),
+            (_, Some(formatted)) => reindent(output, formatted, common_indent(content)),
+        }
+
+        output.push('\n');
+    }
+
+    let _ = write!(output, "</{name}>");
+}
+
+/// Move attributes listed in `order` first, keeping the relative order of others.
+fn sort_attributes<'s, 'a>(
+    attributes: &'s [Attribute<'a>],
+    order: &[String],
+) -> Vec<&'s Attribute<'a>> {
+    let mut attributes = attributes.iter().collect::<Vec<_>>();
+
+    attributes.sort_by_key(|(name, _)| {
+        order
+            .iter()
+            .position(|other| name == other.as_str())
+            .unwrap_or(order.len())
+    });
+
+    attributes
+}
+
+/// Returns the longest indentation shared by all non-blank lines of `content`.
+fn common_indent(content: &str) -> &str {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .reduce(|common, indent| {
+            let len = common
+                .char_indices()
+                .zip(indent.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(common.len().min(indent.len()), |((index, _), _)| index);
+
+            &common[..len]
+        })
+        .unwrap_or_default()
+}
+
+/// Remove the common indentation of `content` and indent non-blank lines by `unit`.
+fn reindent(output: &mut String, content: &str, unit: &str) {
+    let common = common_indent(content);
+
+    for (index, line) in content.split('\n').enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+
+        if line.trim().is_empty() {
+            // Keep a potential `\r`.
+            output.push_str(line.trim_start_matches(|ch: char| ch != '\r' && ch.is_whitespace()));
+        } else {
+            output.push_str(unit);
+            output.push_str(&line[common.len()..]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parser::LineEnding, Block};
+
+    use super::{
+        format, BlockOrder, FormatOptions, Formatter, Indent, LineEndings, QuoteStyle, RawPlacement,
+    };
+
+    const SFC: &str = concat!(
+        "<!-- template -->\n",
+        "<template>\n    <div>\n      <p/>\n    </div>\n</template>\n",
+        "<style scoped lang=\"scss\">\na {}\n</style>\n",
+        "<script lang=\"ts\" setup>\nconst a = '\"';\n</script>\n",
+        "<!-- trailing -->",
+    );
+
+    #[test]
+    fn test_format_default() {
+        let sections = crate::parse(SFC).unwrap();
+
+        assert_eq!(
+            format(&sections, &FormatOptions::default()),
+            concat!(
+                "<!-- template -->\n\n",
+                "<template>\n    <div>\n      <p/>\n    </div>\n</template>\n\n",
+                "<style scoped lang=\"scss\">\na {}\n</style>\n\n",
+                "<script lang=\"ts\" setup>\nconst a = '\"';\n</script>\n\n",
+                "<!-- trailing -->\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let sections = crate::parse(SFC).unwrap();
+
+        let options = FormatOptions {
+            block_order: BlockOrder::ScriptFirst,
+            blank_lines: 0,
+            attribute_order: vec![String::from("setup"), String::from("lang")],
+            quote_style: QuoteStyle::Single,
+            indent: Indent::Blocks {
+                unit: String::from("  "),
+                names: vec![String::from("TEMPLATE")],
+            },
+            raw_placement: RawPlacement::Attach,
+            line_endings: LineEndings::Preserve,
+        };
+
+        assert_eq!(
+            format(&sections, &options),
+            concat!(
+                "<script setup lang='ts'>\nconst a = '\"';\n</script>\n",
+                "<!-- template -->\n",
+                "<template>\n  <div>\n    <p/>\n  </div>\n</template>\n",
+                "<style lang='scss' scoped>\na {}\n</style>\n",
+                "<!-- trailing -->\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_indent_unlisted() {
+        let sections =
+            crate::parse("<template>\n    <p/>\n</template>\n<style>\n    a {}\n  b {}\n</style>")
+                .unwrap();
+
+        let options = FormatOptions {
+            indent: Indent::Blocks {
+                unit: String::from("  "),
+                names: vec![String::from("template")],
+            },
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format(&sections, &options),
+            "<template>\n  <p/>\n</template>\n\n<style>\n    a {}\n  b {}\n</style>\n"
+        );
+    }
+
+    #[test]
+    fn test_format_raw_placement() {
+        let sections = crate::parse(SFC).unwrap();
+
+        let top = format(
+            &sections,
+            &FormatOptions {
+                raw_placement: RawPlacement::Top,
+                ..FormatOptions::default()
+            },
+        );
+        assert!(top.starts_with("<!-- template -->\n\n<!-- trailing -->\n\n<template>"));
+
+        let removed = format(
+            &sections,
+            &FormatOptions {
+                raw_placement: RawPlacement::Remove,
+                ..FormatOptions::default()
+            },
+        );
+        assert!(!removed.contains("<!--"));
+    }
+
+    #[test]
+    fn test_format_line_endings() {
+        let source =
+            "<template>\r\n  <div/>\r\n  <p/>\r\n</template>\r\n<style>\na {}\n</style>\r\n";
+        let sections = crate::parse(source).unwrap();
+
+        assert_eq!(
+            format(&sections, &FormatOptions::default()),
+            "<template>\r\n  <div/>\r\n  <p/>\r\n</template>\r\n\r\n<style>\r\na {}\r\n</style>\r\n"
+        );
+
+        let options = FormatOptions {
+            line_endings: LineEndings::Normalize(LineEnding::Lf),
+            ..FormatOptions::default()
+        };
+
+        assert_eq!(
+            format(&sections, &options),
+            "<template>\n  <div/>\n  <p/>\n</template>\n\n<style>\na {}\n</style>\n"
+        );
+    }
+
+    #[test]
+    fn test_formatter_hooks() {
+        let sections = crate::parse(concat!(
+            "<template>\n  <div>\n    <p/>\n  </div>\n</template>\n",
+            "<script>\nbroken(\n</script>\n",
+            "<style lang=\"scss\">a{}</style>",
+        ))
+        .unwrap();
+
+        let mut formatter = Formatter::new(FormatOptions::default());
+
+        formatter
+            .register("template", None, |content: &str, _: &Block| {
+                assert_eq!(content, "<div>\n  <p/>\n</div>");
+                Ok(content.replace("<p/>", "<p></p>"))
+            })
+            .register("script", None, |_: &str, _: &Block| {
+                Err("syntax error".into())
+            })
+            .register("style", Some("css"), |_: &str, _: &Block| unreachable!())
+            .register("style", Some("scss"), |_: &str, _: &Block| {
+                Ok(String::from("a {\n}\n"))
+            });
+
+        let formatted = formatter.format(&sections);
+
+        assert_eq!(
+            formatted.output,
+            concat!(
+                "<template>\n  <div>\n    <p></p>\n  </div>\n</template>\n\n",
+                "<script>\nbroken(\n</script>\n\n",
+                "<style lang=\"scss\">\na {\n}\n</style>\n",
+            )
+        );
+        assert_eq!(formatted.errors.len(), 1);
+        assert_eq!(formatted.errors[0].index, 1);
+        assert_eq!(formatted.errors[0].name, "script");
+    }
+}
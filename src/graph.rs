@@ -0,0 +1,808 @@
+//! Which components use which.
+//!
+//! A [`ComponentGraph`] links components through:
+//! - imports found in their `<script>` blocks, i.e: `import Button from './Button.vue'`,
+//! - tags found in their `<template>` blocks, i.e: `<MyButton>` or `<my-button>` for a component
+//!   named `MyButton`. Tags are resolved as Vue does: as is, camelized, then capitalized. Native
+//!   HTML & SVG elements, i.e: `<button>`, never refer to a component.
+//!
+//! Imports are found lexically, not by a JavaScript parser. Relative specifiers are resolved from
+//! the directory of the importing component, see [`GraphBuilder::alias`] for other specifiers.
+//!
+//! With the `serde` feature enabled, [`ComponentGraph`] can be serialized, see
+//! [`ComponentGraph::to_dot`] for a Graphviz export.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::{graph::ComponentGraph, Sfc};
+//!
+//! let app = Sfc::parse("<script>import Page from './Page.vue'</script>").unwrap();
+//! let page = Sfc::parse("<template><my-button /></template>").unwrap();
+//! let button = Sfc::parse("<template><button></button></template>").unwrap();
+//! let unused = Sfc::parse("<template></template>").unwrap();
+//!
+//! let mut builder = ComponentGraph::builder();
+//! builder.add("src/App.vue", "App", &app);
+//! builder.add("src/Page.vue", "Page", &page);
+//! builder.add("src/components/MyButton.vue", "MyButton", &button);
+//! builder.add("src/components/Unused.vue", "Unused", &unused);
+//!
+//! let graph = builder.build();
+//!
+//! assert_eq!(graph.edges.len(), 2);
+//! assert!(graph.cycles().is_empty());
+//! assert_eq!(graph.unused().collect::<Vec<_>>(), vec![0, 3]);
+//! ```
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write,
+    path::{Component as PathComponent, Path, PathBuf},
+};
+
+#[cfg(feature = "scan")]
+use crate::scan::{scan_with, ComponentIndex, ScanError, ScanOptions};
+use crate::{
+    parser::{Token, Tokenizer},
+    Sfc,
+};
+
+/// A component, see [`ComponentGraph::nodes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// A usage of a component by another, see [`ComponentGraph::edges`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge {
+    /// Index of the using component.
+    pub from: usize,
+    /// Index of the used component.
+    pub to: usize,
+    pub kind: EdgeKind,
+}
+
+/// How a component uses another, see [`Edge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EdgeKind {
+    /// Imported in a `<script>` block.
+    Import,
+    /// Used as a tag in a `<template>` block.
+    Template,
+}
+
+/// A graph of components and their usages, see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentGraph {
+    /// Components, in the order they were added.
+    pub nodes: Vec<Node>,
+    /// Usages, sorted and without duplicates.
+    pub edges: Vec<Edge>,
+}
+
+impl ComponentGraph {
+    pub fn builder() -> GraphBuilder {
+        GraphBuilder::default()
+    }
+
+    /// Components used by the component at index `node`.
+    pub fn dependencies(&self, node: usize) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(move |edge| edge.from == node)
+    }
+
+    /// Components using the component at index `node`.
+    pub fn dependents(&self, node: usize) -> impl Iterator<Item = &Edge> {
+        self.edges.iter().filter(move |edge| edge.to == node)
+    }
+
+    /// Import cycles: strongly connected components of the import edges with more than one
+    /// component, or a single component importing itself.
+    ///
+    /// Each cycle lists node indices in ascending order, cycles are sorted by their first index.
+    #[must_use]
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut successors = vec![Vec::new(); self.nodes.len()];
+
+        for edge in &self.edges {
+            if edge.kind == EdgeKind::Import {
+                successors[edge.from].push(edge.to);
+            }
+        }
+
+        let mut cycles = strongly_connected(&successors)
+            .into_iter()
+            .filter(|scc| match scc.as_slice() {
+                [node] => successors[*node].contains(node),
+                _ => true,
+            })
+            .map(|mut scc| {
+                scc.sort_unstable();
+                scc
+            })
+            .collect::<Vec<_>>();
+
+        cycles.sort_unstable();
+        cycles
+    }
+
+    /// Indices of the components which no other component uses.
+    ///
+    /// Entry points, i.e: `App.vue` or pages referenced by a router, are reported too.
+    pub fn unused(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.nodes.len()).filter(|&node| self.dependents(node).all(|edge| edge.from == node))
+    }
+
+    /// Print the graph in the Graphviz DOT language.
+    ///
+    /// Template usages are dashed, components part of an import cycle are red.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let in_cycle = self.cycles().into_iter().flatten().collect::<BTreeSet<_>>();
+        let mut dot = String::from("digraph components {\n");
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            let _ = write!(
+                dot,
+                "  n{index} [label={}, tooltip={}",
+                quote(&node.name),
+                quote(&node.path.to_string_lossy())
+            );
+
+            if in_cycle.contains(&index) {
+                dot.push_str(", color=red");
+            }
+
+            dot.push_str("];\n");
+        }
+
+        for edge in &self.edges {
+            let _ = write!(dot, "  n{} -> n{}", edge.from, edge.to);
+
+            if edge.kind == EdgeKind::Template {
+                dot.push_str(" [style=dashed]");
+            }
+
+            dot.push_str(";\n");
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Builds a [`ComponentGraph`], see [`ComponentGraph::builder`].
+#[derive(Clone, Debug, Default)]
+pub struct GraphBuilder {
+    aliases: Vec<(String, PathBuf)>,
+    nodes: Vec<Node>,
+    /// Import specifiers & template tag names of each node.
+    usages: Vec<(Vec<String>, Vec<String>)>,
+}
+
+impl GraphBuilder {
+    /// Resolve import specifiers starting with `prefix` from `path`, i.e: `@/` from `src/`.
+    ///
+    /// Paths are compared lexically, `path` must be relative to the same directory as the paths
+    /// given to [`GraphBuilder::add`].
+    pub fn alias(&mut self, prefix: impl Into<String>, path: impl Into<PathBuf>) -> &mut Self {
+        self.aliases.push((prefix.into(), path.into()));
+        self
+    }
+
+    /// Add a component.
+    ///
+    /// Templates with `lang="pug"` are converted to HTML first, and skipped if that fails.
+    pub fn add(
+        &mut self,
+        path: impl Into<PathBuf>,
+        name: impl Into<String>,
+        sfc: &Sfc,
+    ) -> &mut Self {
+        let mut imports = Vec::new();
+        let mut tags = Vec::new();
+
+        for block in sfc.blocks() {
+            if block.name == "script" {
+                imports.extend(import_specifiers(&block.content).map(str::to_owned));
+            } else if block.name == "template" {
+                let html = match block.attribute_value("lang").map(|lang| lang.as_str()) {
+                    Some("pug" | "jade") => match crate::pug::to_html(&block.content) {
+                        Ok(html) => html.html,
+                        Err(_) => continue,
+                    },
+                    _ => block.content.clone().into_owned(),
+                };
+
+                tags.extend(Tokenizer::new(&html).filter_map(|token| match token {
+                    Token::StartTag { name, .. } => Some(name.to_string()),
+                    _ => None,
+                }));
+            }
+        }
+
+        self.nodes.push(Node {
+            name: name.into(),
+            path: normalize(&path.into()),
+        });
+        self.usages.push((imports, tags));
+
+        self
+    }
+
+    /// Walk `root` with [`scan`][crate::scan::scan] and add every parsed component, paths
+    /// relative to `root`.
+    ///
+    /// # Errors
+    /// See [`scan`][crate::scan::scan].
+    #[cfg(feature = "scan")]
+    pub fn add_project(
+        &mut self,
+        root: impl AsRef<Path>,
+        options: &ScanOptions,
+    ) -> Result<ComponentIndex, ScanError> {
+        scan_with(root.as_ref(), options, |component, sfc| {
+            self.add(&component.path, &component.name, sfc);
+        })
+    }
+
+    /// Resolve usages between the added components.
+    ///
+    /// A tag used by several components with the same name links to all of them.
+    #[must_use]
+    pub fn build(&self) -> ComponentGraph {
+        let by_path = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.path.as_path(), index))
+            .collect::<HashMap<_, _>>();
+
+        let mut by_name = HashMap::<_, Vec<usize>>::new();
+
+        for (index, node) in self.nodes.iter().enumerate() {
+            by_name.entry(node.name.as_str()).or_default().push(index);
+        }
+
+        let mut edges = BTreeSet::new();
+
+        for (from, (imports, tags)) in self.usages.iter().enumerate() {
+            for specifier in imports {
+                if let Some(&to) = self
+                    .candidates(&self.nodes[from].path, specifier)
+                    .iter()
+                    .find_map(|path| by_path.get(path.as_path()))
+                {
+                    edges.insert(Edge {
+                        from,
+                        to,
+                        kind: EdgeKind::Import,
+                    });
+                }
+            }
+
+            for tag in tags.iter().filter(|tag| !is_native_tag(tag)) {
+                for &to in component_names(tag)
+                    .iter()
+                    .find_map(|name| by_name.get(name.as_str()))
+                    .into_iter()
+                    .flatten()
+                {
+                    edges.insert(Edge {
+                        from,
+                        to,
+                        kind: EdgeKind::Template,
+                    });
+                }
+            }
+        }
+
+        ComponentGraph {
+            nodes: self.nodes.clone(),
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    /// Paths `specifier` may refer to when imported from `from`.
+    fn candidates(&self, from: &Path, specifier: &str) -> Vec<PathBuf> {
+        let base = if specifier.starts_with("./") || specifier.starts_with("../") {
+            from.parent().unwrap_or(Path::new("")).join(specifier)
+        } else if let Some((prefix, path)) = self
+            .aliases
+            .iter()
+            .find(|(prefix, _)| specifier.starts_with(prefix.as_str()))
+        {
+            path.join(&specifier[prefix.len()..])
+        } else {
+            return Vec::new();
+        };
+
+        let base = normalize(&base);
+
+        let mut with_extension = base.clone().into_os_string();
+        with_extension.push(".vue");
+
+        let index = base.join("index.vue");
+
+        vec![base, PathBuf::from(with_extension), index]
+    }
+}
+
+/// Lexically remove `.` & `..` components.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            PathComponent::CurDir => {}
+            PathComponent::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(PathComponent::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// HTML & SVG elements, as known by Vue.
+const NATIVE_TAGS: &[&str] = &[
+    // HTML
+    "a",
+    "abbr",
+    "address",
+    "area",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "base",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "body",
+    "br",
+    "button",
+    "canvas",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "data",
+    "datalist",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "ins",
+    "kbd",
+    "label",
+    "legend",
+    "li",
+    "link",
+    "main",
+    "map",
+    "mark",
+    "menu",
+    "meta",
+    "meter",
+    "nav",
+    "noscript",
+    "object",
+    "ol",
+    "optgroup",
+    "option",
+    "output",
+    "p",
+    "param",
+    "picture",
+    "pre",
+    "progress",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "script",
+    "search",
+    "section",
+    "select",
+    "slot",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "style",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "template",
+    "textarea",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "title",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+    // SVG
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "discard",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "foreignObject",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "set",
+    "stop",
+    "svg",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "tspan",
+    "use",
+    "view",
+];
+
+/// Whether `tag` is an HTML or SVG element rather than a component.
+fn is_native_tag(tag: &str) -> bool {
+    NATIVE_TAGS.contains(&tag)
+}
+
+/// Component names `tag` resolves to, in the order Vue tries them: as is, camelized &
+/// capitalized, i.e: `my-button`, `myButton` & `MyButton`.
+fn component_names(tag: &str) -> [String; 3] {
+    let mut camelized = String::with_capacity(tag.len());
+    let mut chars = tag.chars();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '-' => match chars.next() {
+                Some(next) if next.is_ascii_alphanumeric() || next == '_' => {
+                    camelized.push(next.to_ascii_uppercase());
+                }
+                Some(next) => {
+                    camelized.push('-');
+                    camelized.push(next);
+                }
+                None => camelized.push('-'),
+            },
+            ch => camelized.push(ch),
+        }
+    }
+
+    let mut capitalized = camelized.clone();
+    if let Some(first) = capitalized.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+
+    [tag.to_owned(), camelized, capitalized]
+}
+
+/// Specifiers of `import ... from '...'`, `export ... from '...'`, `import '...'` &
+/// `import('...')` statements, in order.
+fn import_specifiers(script: &str) -> impl Iterator<Item = &str> {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$';
+
+    let mut specifiers = ["import", "from"]
+        .into_iter()
+        .flat_map(|keyword| {
+            script.match_indices(keyword).filter_map(move |(index, _)| {
+                let rest = &script[index + keyword.len()..];
+
+                if script[..index].ends_with(is_ident) || rest.starts_with(is_ident) {
+                    return None;
+                }
+
+                let rest = rest.trim_start();
+                let rest = match keyword {
+                    "import" => rest.strip_prefix('(').map_or(rest, str::trim_start),
+                    _ => rest,
+                };
+
+                let quote = rest
+                    .chars()
+                    .next()
+                    .filter(|ch| matches!(ch, '\'' | '"' | '`'))?;
+                let rest = &rest[1..];
+
+                rest.find(quote).map(|end| (index, &rest[..end]))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    specifiers.sort_unstable_by_key(|&(index, _)| index);
+    specifiers.into_iter().map(|(_, specifier)| specifier)
+}
+
+/// Tarjan's strongly connected components algorithm, iterative to support deep graphs.
+fn strongly_connected(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut index = vec![UNVISITED; successors.len()];
+    let mut low_link = vec![0; successors.len()];
+    let mut on_stack = vec![false; successors.len()];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut sccs = Vec::new();
+
+    for root in 0..successors.len() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        // Nodes being visited, along with the position of their next successor.
+        let mut calls = vec![(root, 0)];
+        index[root] = next_index;
+        low_link[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some(&mut (node, ref mut position)) = calls.last_mut() {
+            if let Some(&successor) = successors[node].get(*position) {
+                *position += 1;
+
+                if index[successor] == UNVISITED {
+                    index[successor] = next_index;
+                    low_link[successor] = next_index;
+                    next_index += 1;
+                    stack.push(successor);
+                    on_stack[successor] = true;
+                    calls.push((successor, 0));
+                } else if on_stack[successor] {
+                    low_link[node] = low_link[node].min(index[successor]);
+                }
+
+                continue;
+            }
+
+            calls.pop();
+
+            if let Some(&(parent, _)) = calls.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+
+            if low_link[node] == index[node] {
+                let mut scc = Vec::new();
+
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    scc.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                sccs.push(scc);
+            }
+        }
+    }
+
+    sccs
+}
+
+/// Quote a DOT identifier.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Sfc;
+
+    use super::{
+        component_names, import_specifiers, strongly_connected, ComponentGraph, Edge, EdgeKind,
+    };
+
+    #[test]
+    fn test_import_specifiers() {
+        let script = "import A from './A.vue'\n\
+            import { b } from \"../b\"\n\
+            import './side-effect.css'\n\
+            export { C } from '@/C.vue'\n\
+            const D = () => import('./D.vue')\n\
+            const reimport = 'x', fromage = 'y'";
+
+        assert_eq!(
+            import_specifiers(script).collect::<Vec<_>>(),
+            ["./A.vue", "../b", "./side-effect.css", "@/C.vue", "./D.vue"]
+        );
+    }
+
+    #[test]
+    fn test_component_names() {
+        assert_eq!(
+            component_names("my-button"),
+            ["my-button", "myButton", "MyButton"]
+        );
+        assert_eq!(component_names("MyButton"), ["MyButton"; 3]);
+        assert_eq!(
+            component_names("my_button"),
+            ["my_button", "my_button", "My_button"]
+        );
+    }
+
+    #[test]
+    fn test_native_tags() {
+        let template = |template: &str| {
+            Sfc::parse(&format!("<template>{template}</template>"))
+                .unwrap()
+                .into_owned()
+        };
+
+        let mut builder = ComponentGraph::builder();
+        builder.add("Button.vue", "Button", &template("<button></button>"));
+        builder.add("MyButton.vue", "MyButton", &template(""));
+        builder.add("A.vue", "A", &template("<button></button><my_button/>"));
+        builder.add("B.vue", "B", &template("<Button/><my-button/>"));
+
+        let graph = builder.build();
+
+        assert_eq!(graph.dependencies(0).count(), 0);
+        assert_eq!(graph.dependencies(2).count(), 0);
+        assert_eq!(
+            graph
+                .dependencies(3)
+                .map(|edge| edge.to)
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+    }
+
+    #[test]
+    fn test_strongly_connected() {
+        let mut sccs = strongly_connected(&[vec![1], vec![2], vec![0, 3], vec![3], vec![]])
+            .into_iter()
+            .map(|mut scc| {
+                scc.sort_unstable();
+                scc
+            })
+            .collect::<Vec<_>>();
+        sccs.sort_unstable();
+
+        assert_eq!(sccs, [vec![0, 1, 2], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn test_graph() {
+        let sfc = |script: &str, template: &str| {
+            Sfc::parse(&format!(
+                "<script>{script}</script><template>{template}</template>"
+            ))
+            .unwrap()
+            .into_owned()
+        };
+
+        let mut builder = ComponentGraph::builder();
+        builder.alias("@/", "src");
+        builder.add("src/A.vue", "A", &sfc("import B from './b/B.vue'", "<B/>"));
+        builder.add("src/b/B.vue", "B", &sfc("import C from '@/c'", ""));
+        builder.add("src/c/index.vue", "C", &sfc("import A from '../A.vue'", ""));
+        builder.add("src/D.vue", "D", &sfc("import D from './D.vue'", ""));
+        builder.add(
+            "src/E.vue",
+            "ECard",
+            &Sfc::parse("<template lang=\"pug\">\ndiv\n  c\n</template>").unwrap(),
+        );
+
+        let graph = builder.build();
+
+        assert_eq!(
+            graph.dependencies(0).copied().collect::<Vec<_>>(),
+            [
+                Edge {
+                    from: 0,
+                    to: 1,
+                    kind: EdgeKind::Import
+                },
+                Edge {
+                    from: 0,
+                    to: 1,
+                    kind: EdgeKind::Template
+                },
+            ]
+        );
+        assert_eq!(graph.cycles(), [vec![0, 1, 2], vec![3]]);
+        assert_eq!(graph.unused().collect::<Vec<_>>(), [3, 4]);
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph components {\n"));
+        assert!(dot.contains("  n0 [label=\"A\", tooltip=\"src/A.vue\", color=red];\n"));
+        assert!(dot.contains("  n0 -> n1 [style=dashed];\n"));
+        assert!(dot.contains("  n4 [label=\"ECard\", tooltip=\"src/E.vue\"];\n"));
+        assert!(dot.contains("  n4 -> n2 [style=dashed];\n"));
+    }
+}
@@ -5,14 +5,38 @@
 //! See [`parse`].
 //!
 //! ## Printing
-//! [`Block`], [`Raw`] and [`Section`] implement [`std::fmt::Display`].
+//! [`Block`], [`Raw`], [`Section`] and [`Sfc`] implement [`std::fmt::Display`].
+//!
+//! ## Batch parsing
+//! See [`batch`], enable the `rayon` feature to parse files in parallel.
+//!
+//! ## Project scanning
+//! See `scan`, behind the `scan` feature.
+//!
+//! ## Traversal
+//! See [`visit`].
+//!
+//! ## `@vue/compiler-sfc` compatibility
+//! See [`descriptor`], enable the `serde` feature to serialize to the same JSON shape.
 
 #[doc(no_inline)]
-pub use self::ast::{Attribute, AttributeName, AttributeValue, Block, BlockName, Raw, Section};
+pub use self::ast::{
+    Attribute, AttributeName, AttributeValue, Block, BlockName, Raw, Section, Sfc,
+};
 pub use self::error::Error;
 #[doc(no_inline)]
 pub use self::parser::parse;
 
 pub mod ast;
+pub mod batch;
+pub mod descriptor;
 mod error;
+pub mod format;
+pub mod graph;
+pub mod lint;
 pub mod parser;
+pub mod pug;
+#[cfg(feature = "scan")]
+pub mod scan;
+pub mod split;
+pub mod visit;
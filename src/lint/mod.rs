@@ -0,0 +1,277 @@
+//! A rule engine over parsed Vue SFCs.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::lint::{apply_fixes, Linter, Severity};
+//!
+//! let source = "<template></template>\n<style>a {}</style>";
+//!
+//! let mut linter = Linter::default();
+//! linter.set_severity("no-empty-blocks", Severity::Off);
+//!
+//! let diagnostics = linter.lint(source).unwrap();
+//!
+//! assert_eq!(diagnostics.len(), 1);
+//! assert_eq!(diagnostics[0].rule, "style-scoped");
+//! assert_eq!(
+//!     apply_fixes(source, &diagnostics),
+//!     "<template></template>\n<style scoped>a {}</style>"
+//! );
+//! ```
+
+use std::{fmt::Display, ops::Range};
+
+use crate::{
+    parser::{parse_with_spans, ParseError, SectionSpan},
+    Block, Section,
+};
+
+pub mod rules;
+
+/// Severity of a [`Diagnostic`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The rule is disabled.
+    Off,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Replace `range` of the source with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+/// A problem found by a [`Rule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub message: String,
+    /// Byte range of the offending source.
+    pub span: Range<usize>,
+    /// Edits fixing the problem, empty if it can't be fixed automatically.
+    pub fix: Vec<Edit>,
+}
+
+/// A [`Violation`] reported by a [`Linter`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Name of the rule, see [`Rule::name`].
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub message: String,
+    /// Byte range of the offending source.
+    pub span: Range<usize>,
+    /// Edits fixing the problem, empty if it can't be fixed automatically.
+    pub fix: Vec<Edit>,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)
+    }
+}
+
+/// A parsed Vue SFC, as seen by rules.
+#[derive(Debug)]
+pub struct Context<'a> {
+    source: &'a str,
+    sections: Vec<(Section<'a>, SectionSpan)>,
+}
+
+impl<'a> Context<'a> {
+    /// Parse `source`.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn new(source: &'a str) -> Result<Self, ParseError> {
+        Ok(Self {
+            source,
+            sections: parse_with_spans(source)?,
+        })
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Sections along with their spans, in source order.
+    #[must_use]
+    pub fn sections(&self) -> &[(Section<'a>, SectionSpan)] {
+        &self.sections
+    }
+
+    /// Blocks along with their spans, in source order.
+    pub fn blocks(&self) -> impl Iterator<Item = (&Block<'a>, &SectionSpan)> {
+        self.sections
+            .iter()
+            .filter_map(|(section, span)| match section {
+                Section::Block(block) => Some((block, span)),
+                Section::Raw(_) => None,
+            })
+    }
+}
+
+/// A lint rule.
+pub trait Rule {
+    /// Unique name of the rule, i.e: `no-empty-blocks`.
+    fn name(&self) -> &'static str;
+
+    /// Severity used unless configured with [`Linter::set_severity`].
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    /// Report violations of the rule in `context`.
+    fn check(&self, context: &Context) -> Vec<Violation>;
+}
+
+/// Runs a set of rules, each with a configured [`Severity`].
+///
+/// [`Linter::default`] runs all the rules in [`rules`] with their default configuration.
+pub struct Linter {
+    rules: Vec<(Box<dyn Rule + Send + Sync>, Severity)>,
+}
+
+impl Linter {
+    /// Returns a linter without any rule.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    /// Add a rule, with its default severity.
+    ///
+    /// A rule with the same name is replaced.
+    pub fn add_rule(&mut self, rule: impl Rule + Send + Sync + 'static) -> &mut Self {
+        let severity = rule.default_severity();
+        let name = rule.name();
+
+        self.rules.retain(|(other, _)| other.name() != name);
+        self.rules.push((Box::new(rule), severity));
+
+        self
+    }
+
+    /// Set the severity of the rule named `name`, [`Severity::Off`] disables it.
+    ///
+    /// Unknown names are ignored.
+    pub fn set_severity(&mut self, name: &str, severity: Severity) -> &mut Self {
+        for (rule, rule_severity) in &mut self.rules {
+            if rule.name() == name {
+                *rule_severity = severity;
+            }
+        }
+
+        self
+    }
+
+    /// Parse `source` and run enabled rules on it.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn lint(&self, source: &str) -> Result<Vec<Diagnostic>, ParseError> {
+        Ok(self.lint_context(&Context::new(source)?))
+    }
+
+    /// Run enabled rules, diagnostics are sorted by position.
+    #[must_use]
+    pub fn lint_context(&self, context: &Context) -> Vec<Diagnostic> {
+        let mut diagnostics = self
+            .rules
+            .iter()
+            .filter(|(_, severity)| *severity != Severity::Off)
+            .flat_map(|(rule, severity)| {
+                rule.check(context)
+                    .into_iter()
+                    .map(move |violation| Diagnostic {
+                        rule: rule.name(),
+                        severity: *severity,
+                        message: violation.message,
+                        span: violation.span,
+                        fix: violation.fix,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.span.start, diagnostic.span.end));
+
+        diagnostics
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        let mut linter = Self::new();
+
+        linter
+            .add_rule(rules::BlockOrder::default())
+            .add_rule(rules::ScriptLang::default())
+            .add_rule(rules::StyleScoped)
+            .add_rule(rules::NoEmptyBlocks)
+            .add_rule(rules::NoUnknownCustomBlocks::default())
+            .add_rule(rules::NoDuplicateAttributes);
+
+        linter
+    }
+}
+
+impl std::fmt::Debug for Linter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.rules
+                    .iter()
+                    .map(|(rule, severity)| (rule.name(), severity)),
+            )
+            .finish()
+    }
+}
+
+/// Apply the fixes of `diagnostics` to `source`.
+///
+/// A fix overlapping a previously applied one is skipped, run the linter again to apply it.
+#[must_use]
+pub fn apply_fixes(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut edits: Vec<&Edit> = Vec::new();
+
+    for diagnostic in diagnostics {
+        let overlaps = diagnostic.fix.iter().any(|edit| {
+            edits.iter().any(|other| {
+                edit.range.start < other.range.end && other.range.start < edit.range.end
+                    || edit.range.start == other.range.start
+            })
+        });
+
+        if !overlaps {
+            edits.extend(&diagnostic.fix);
+        }
+    }
+
+    edits.sort_by_key(|edit| (edit.range.start, edit.range.end));
+
+    let mut output = String::with_capacity(source.len());
+    let mut offset = 0;
+
+    for edit in edits {
+        output.push_str(&source[offset..edit.range.start]);
+        output.push_str(&edit.replacement);
+        offset = edit.range.end;
+    }
+
+    output.push_str(&source[offset..]);
+
+    output
+}
@@ -0,0 +1,370 @@
+//! Built-in [`Rule`]s.
+
+use std::collections::HashSet;
+
+use crate::{format, AttributeValue, Block};
+
+use super::{Context, Edit, Rule, Violation};
+
+/// Edit inserting ` {attribute}` right after the name of `block` in its start tag.
+fn insert_attribute(block: &Block, start_tag: usize, attribute: &str) -> Edit {
+    let offset = start_tag + 1 + block.name.len();
+
+    Edit {
+        range: offset..offset,
+        replacement: format!(" {attribute}"),
+    }
+}
+
+/// `block-order`: blocks must follow the configured order.
+#[derive(Clone, Debug)]
+pub struct BlockOrder {
+    /// Defaults to [`format::BlockOrder::ScriptFirst`].
+    pub order: format::BlockOrder,
+}
+
+impl Default for BlockOrder {
+    fn default() -> Self {
+        Self {
+            order: format::BlockOrder::ScriptFirst,
+        }
+    }
+}
+
+impl Rule for BlockOrder {
+    fn name(&self) -> &'static str {
+        "block-order"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut last: Option<(usize, &Block)> = None;
+
+        for (block, span) in context.blocks() {
+            let rank = self.order.rank(block);
+
+            match last {
+                Some((last_rank, last_block)) if rank < last_rank => {
+                    violations.push(Violation {
+                        message: format!(
+                            "`{}` block should come before `{}` block",
+                            block.name, last_block.name
+                        ),
+                        span: span.start_tag.clone(),
+                        fix: Vec::new(),
+                    });
+                }
+                _ => last = Some((rank, block)),
+            }
+        }
+
+        violations
+    }
+}
+
+/// `script-lang`: `<script>` blocks must have the configured `lang` attribute.
+#[derive(Clone, Debug)]
+pub struct ScriptLang {
+    /// Accepted languages, the first one is used to fix missing `lang` attributes.
+    /// Defaults to `["ts"]`.
+    pub langs: Vec<String>,
+}
+
+impl Default for ScriptLang {
+    fn default() -> Self {
+        Self {
+            langs: vec![String::from("ts")],
+        }
+    }
+}
+
+impl Rule for ScriptLang {
+    fn name(&self) -> &'static str {
+        "script-lang"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        context
+            .blocks()
+            .filter(|(block, _)| block.name == "script")
+            .filter_map(|(block, span)| {
+                match block.attribute_value("lang").map(AttributeValue::as_str) {
+                    Some(lang) if self.langs.iter().any(|other| other == lang) => None,
+                    Some(lang) => Some(Violation {
+                        message: format!("`<script>` uses unexpected `lang=\"{lang}\"`"),
+                        span: span.start_tag.clone(),
+                        fix: Vec::new(),
+                    }),
+                    None => Some(Violation {
+                        message: String::from("`<script>` is missing a `lang` attribute"),
+                        span: span.start_tag.clone(),
+                        fix: self
+                            .langs
+                            .first()
+                            .map(|lang| {
+                                insert_attribute(
+                                    block,
+                                    span.start_tag.start,
+                                    &format!("lang=\"{lang}\""),
+                                )
+                            })
+                            .into_iter()
+                            .collect(),
+                    }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// `style-scoped`: `<style>` blocks must be `scoped` or a `module`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StyleScoped;
+
+impl Rule for StyleScoped {
+    fn name(&self) -> &'static str {
+        "style-scoped"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        context
+            .blocks()
+            .filter(|(block, _)| {
+                block.name == "style"
+                    && block.attribute("scoped").is_none()
+                    && block.attribute("module").is_none()
+            })
+            .map(|(block, span)| Violation {
+                message: String::from("`<style>` should be `scoped` or a `module`"),
+                span: span.start_tag.clone(),
+                fix: vec![insert_attribute(block, span.start_tag.start, "scoped")],
+            })
+            .collect()
+    }
+}
+
+/// `no-empty-blocks`: blocks must have content, unless they have a `src` attribute.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoEmptyBlocks;
+
+impl Rule for NoEmptyBlocks {
+    fn name(&self) -> &'static str {
+        "no-empty-blocks"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        context
+            .blocks()
+            .filter(|(block, _)| {
+                block.content.trim().is_empty() && block.attribute("src").is_none()
+            })
+            .map(|(block, span)| {
+                let source = context.source();
+
+                // Also remove the line break following the block.
+                let end = source[span.outer.end..]
+                    .strip_prefix("\r\n")
+                    .or_else(|| source[span.outer.end..].strip_prefix('\n'))
+                    .map_or(span.outer.end, |rest| source.len() - rest.len());
+
+                Violation {
+                    message: format!("`<{}>` is empty", block.name),
+                    span: span.outer.clone(),
+                    fix: vec![Edit {
+                        range: span.outer.start..end,
+                        replacement: String::new(),
+                    }],
+                }
+            })
+            .collect()
+    }
+}
+
+/// `no-unknown-custom-blocks`: custom blocks must be explicitly allowed.
+#[derive(Clone, Debug, Default)]
+pub struct NoUnknownCustomBlocks {
+    /// Allowed custom block names, i.e: `i18n`. Defaults to an empty list.
+    pub allowed: Vec<String>,
+}
+
+impl Rule for NoUnknownCustomBlocks {
+    fn name(&self) -> &'static str {
+        "no-unknown-custom-blocks"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        context
+            .blocks()
+            .filter(|(block, _)| {
+                !["template", "script", "style"]
+                    .into_iter()
+                    .chain(self.allowed.iter().map(String::as_str))
+                    .any(|name| block.name == name)
+            })
+            .map(|(block, span)| Violation {
+                message: format!("unknown custom block `<{}>`", block.name),
+                span: span.start_tag.clone(),
+                fix: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// `no-duplicate-attributes`: attributes of a block must have different names.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoDuplicateAttributes;
+
+impl Rule for NoDuplicateAttributes {
+    fn name(&self) -> &'static str {
+        "no-duplicate-attributes"
+    }
+
+    fn check(&self, context: &Context) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        for (block, span) in context.blocks() {
+            let mut seen = HashSet::new();
+            let mut previous_end = span.start_tag.start + 1 + block.name.len();
+
+            for ((name, _), attribute_span) in block.attributes.iter().zip(&span.attributes) {
+                if !seen.insert(name) {
+                    violations.push(Violation {
+                        message: format!("duplicate attribute `{name}` on `<{}>`", block.name),
+                        span: attribute_span.clone(),
+                        fix: vec![Edit {
+                            range: previous_end..attribute_span.end,
+                            replacement: String::new(),
+                        }],
+                    });
+                }
+
+                previous_end = attribute_span.end;
+            }
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lint::{apply_fixes, Linter, Rule, Severity};
+
+    use super::{
+        BlockOrder, NoDuplicateAttributes, NoEmptyBlocks, NoUnknownCustomBlocks, ScriptLang,
+        StyleScoped,
+    };
+
+    fn check(rule: impl Rule + Send + Sync + 'static, source: &str) -> (Vec<String>, String) {
+        let mut linter = Linter::new();
+        linter.add_rule(rule);
+
+        let diagnostics = linter.lint(source).unwrap();
+
+        (
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.message.clone())
+                .collect(),
+            apply_fixes(source, &diagnostics),
+        )
+    }
+
+    #[test]
+    fn test_block_order() {
+        let (messages, _) = check(
+            BlockOrder::default(),
+            "<template></template><script></script><style></style>",
+        );
+
+        assert_eq!(
+            messages,
+            vec!["`script` block should come before `template` block"]
+        );
+    }
+
+    #[test]
+    fn test_script_lang() {
+        let (messages, fixed) = check(
+            ScriptLang::default(),
+            "<script setup></script><script lang=\"js\"></script><script lang=\"ts\"></script>",
+        );
+
+        assert_eq!(
+            messages,
+            vec![
+                "`<script>` is missing a `lang` attribute",
+                "`<script>` uses unexpected `lang=\"js\"`"
+            ]
+        );
+        assert!(fixed.starts_with("<script lang=\"ts\" setup></script>"));
+    }
+
+    #[test]
+    fn test_style_scoped() {
+        let (messages, fixed) = check(
+            StyleScoped,
+            "<style></style><style scoped></style><style module></style>",
+        );
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            fixed,
+            "<style scoped></style><style scoped></style><style module></style>"
+        );
+    }
+
+    #[test]
+    fn test_no_empty_blocks() {
+        let (messages, fixed) = check(
+            NoEmptyBlocks,
+            "<template>\n</template>\n<script src=\"./a.ts\"></script>\n<style>a {}</style>",
+        );
+
+        assert_eq!(messages, vec!["`<template>` is empty"]);
+        assert_eq!(
+            fixed,
+            "<script src=\"./a.ts\"></script>\n<style>a {}</style>"
+        );
+    }
+
+    #[test]
+    fn test_no_unknown_custom_blocks() {
+        let (messages, _) = check(
+            NoUnknownCustomBlocks {
+                allowed: vec![String::from("i18n")],
+            },
+            "<i18n></i18n><docs></docs>",
+        );
+
+        assert_eq!(messages, vec!["unknown custom block `<docs>`"]);
+    }
+
+    #[test]
+    fn test_no_duplicate_attributes() {
+        let (messages, fixed) = check(
+            NoDuplicateAttributes,
+            "<script lang=\"ts\" setup lang=\"js\"></script>",
+        );
+
+        assert_eq!(messages, vec!["duplicate attribute `lang` on `<script>`"]);
+        assert_eq!(fixed, "<script lang=\"ts\" setup></script>");
+    }
+
+    #[test]
+    fn test_severity() {
+        let mut linter = Linter::default();
+        linter
+            .set_severity("style-scoped", Severity::Error)
+            .set_severity("no-empty-blocks", Severity::Off);
+
+        let diagnostics = linter.lint("<style></style>").unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "error[style-scoped]: `<style>` should be `scoped` or a `module`"
+        );
+    }
+}
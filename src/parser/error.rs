@@ -5,17 +5,61 @@ use std::fmt::Display;
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ParseError {
-    MissingEndTag(#[doc(hidden)] String),
-    UnexpectedEndTag(#[doc(hidden)] String),
+    MissingEndTag(#[doc(hidden)] String, #[doc(hidden)] usize),
+    UnexpectedEndTag(#[doc(hidden)] String, #[doc(hidden)] usize),
+    /// See [`DuplicateAttributes::Error`][super::DuplicateAttributes::Error].
+    DuplicateAttribute(#[doc(hidden)] String, #[doc(hidden)] usize),
+}
+
+impl ParseError {
+    /// Byte offset in the input at which the error occurred:
+    /// - the start tag of the unclosed block for a missing end tag,
+    /// - the duplicate attribute for a duplicate attribute,
+    /// - the unexpected end tag otherwise.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::MissingEndTag(_, offset)
+            | Self::UnexpectedEndTag(_, offset)
+            | Self::DuplicateAttribute(_, offset) => *offset,
+        }
+    }
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::MissingEndTag(name) => write!(f, "missing end tag: `{name}`"),
-            Self::UnexpectedEndTag(name) => write!(f, "unexpected end tag: `{name}`"),
+            Self::MissingEndTag(name, _) => write!(f, "missing end tag: `{name}`"),
+            Self::UnexpectedEndTag(name, _) => write!(f, "unexpected end tag: `{name}`"),
+            Self::DuplicateAttribute(name, _) => write!(f, "duplicate attribute: `{name}`"),
         }
     }
 }
 
 impl Error for ParseError {}
+
+/// A recoverable parsing error, see [`Parsed::warnings`][super::Parsed::warnings].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// See [`DuplicateAttributes::Warn`][super::DuplicateAttributes::Warn].
+    DuplicateAttribute(#[doc(hidden)] String, #[doc(hidden)] usize),
+}
+
+impl ParseWarning {
+    /// Byte offset in the input at which the warning occurred.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::DuplicateAttribute(_, offset) => *offset,
+        }
+    }
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateAttribute(name, _) => write!(f, "duplicate attribute: `{name}`"),
+        }
+    }
+}
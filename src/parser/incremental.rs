@@ -0,0 +1,275 @@
+use std::ops::Range;
+
+use crate::Section;
+
+use super::{section_from_span, ParseError, ParseOptions, ParseWarning, SectionSpan, Sections};
+
+/// Replace `range` of the previous source with `replacement`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+/// A parsed Vue SFC which can be updated after an edit of its source, see
+/// [`Incremental::reparse`].
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::parser::{Incremental, TextEdit};
+///
+/// let source = "<template><p>a</p></template>\n<script>a</script>\n";
+/// let parsed = Incremental::parse(source).unwrap();
+///
+/// let edited = "<template><p>a</p></template>\n<script>ab</script>\n";
+/// let edit = TextEdit {
+///     range: 39..39,
+///     replacement: String::from("b"),
+/// };
+///
+/// let reparsed = parsed.reparse(edited, &edit).unwrap();
+///
+/// // Only the `<script>` block was re-scanned.
+/// assert_eq!(reparsed.changed(), 1..2);
+/// assert_eq!(reparsed.sections(), vue_sfc::parse(edited).unwrap());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Incremental<'a> {
+    options: ParseOptions,
+    sections: Vec<(Section<'a>, SectionSpan)>,
+    warnings: Vec<ParseWarning>,
+    changed: Range<usize>,
+    len: usize,
+}
+
+impl<'a> Incremental<'a> {
+    /// Parse the given input as a Vue SFC.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse(input: &'a str) -> Result<Self, ParseError> {
+        Self::parse_with_options(input, ParseOptions::default())
+    }
+
+    /// Same as [`Incremental::parse`], with the given options.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse_with_options(input: &'a str, options: ParseOptions) -> Result<Self, ParseError> {
+        let mut iter = Sections::with_options(input, options.clone());
+        let sections = std::iter::from_fn(|| iter.next_spanned().transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+        let warnings = iter.warnings;
+
+        Ok(Self {
+            options,
+            changed: 0..sections.len(),
+            sections,
+            warnings,
+            len: input.len(),
+        })
+    }
+
+    /// Parse `source`, the previous source with `edit` applied.
+    ///
+    /// If `edit` is within the content of a block, only this block is re-scanned: other sections
+    /// are re-sliced from `source` using their (shifted) span. Otherwise, or if the edit changes
+    /// the boundaries of the block (i.e: it inserts its end tag), `source` is parsed again.
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn reparse<'b>(
+        &self,
+        source: &'b str,
+        edit: &TextEdit,
+    ) -> Result<Incremental<'b>, ParseError> {
+        let TextEdit { range, replacement } = edit;
+
+        let is_consistent = range.start <= range.end
+            && range.end <= self.len
+            && source.len() + range.len() == self.len + replacement.len()
+            && source.get(range.start..range.start + replacement.len())
+                == Some(replacement.as_str());
+
+        let edited = self.sections.iter().position(|(section, span)| {
+            matches!(section, Section::Block(_))
+                // Not a self-closing block.
+                && span.start_tag.end < span.outer.end
+                && span.content.start <= range.start
+                && range.end <= span.content.end
+        });
+
+        let Some(index) = edited.filter(|_| is_consistent) else {
+            return Incremental::parse_with_options(source, self.options.clone());
+        };
+
+        let shift = |range: &Range<usize>| {
+            range.start + replacement.len() - edit.range.len()
+                ..range.end + replacement.len() - edit.range.len()
+        };
+
+        let mut sections = Vec::with_capacity(self.sections.len());
+        let mut warnings = Vec::new();
+
+        for (_, span) in &self.sections[..index] {
+            match section_from_span(source, span, &self.options, &mut warnings)? {
+                Some(section) => sections.push((section, span.clone())),
+                None => return Incremental::parse_with_options(source, self.options.clone()),
+            }
+        }
+
+        let outer = &self.sections[index].1.outer;
+        let end = shift(outer).end;
+        let mut rescan = Sections::starting_at(&source[..end], outer.start, self.options.clone());
+
+        match rescan.next_spanned() {
+            Ok(Some((section @ Section::Block(_), span))) if span.outer == (outer.start..end) => {
+                sections.push((section, span));
+                warnings.append(&mut rescan.warnings);
+            }
+            _ => return Incremental::parse_with_options(source, self.options.clone()),
+        }
+
+        for (_, span) in &self.sections[index + 1..] {
+            let span = SectionSpan {
+                outer: shift(&span.outer),
+                content: shift(&span.content),
+                start_tag: shift(&span.start_tag),
+                attributes: span.attributes.iter().map(shift).collect(),
+            };
+
+            match section_from_span(source, &span, &self.options, &mut warnings)? {
+                Some(section) => sections.push((section, span)),
+                None => return Incremental::parse_with_options(source, self.options.clone()),
+            }
+        }
+
+        Ok(Incremental {
+            options: self.options.clone(),
+            sections,
+            warnings,
+            changed: index..index + 1,
+            len: source.len(),
+        })
+    }
+
+    /// Sections along with their spans, in source order.
+    #[must_use]
+    pub fn spanned_sections(&self) -> &[(Section<'a>, SectionSpan)] {
+        &self.sections
+    }
+
+    /// Sections, in source order.
+    #[must_use]
+    pub fn sections(&self) -> Vec<Section<'a>> {
+        self.sections
+            .iter()
+            .map(|(section, _)| section.clone())
+            .collect()
+    }
+
+    /// Recoverable errors, in order of occurrence.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Indices of the sections which were re-scanned by the last [`Incremental::reparse`]
+    /// (or [`Incremental::parse`]), other sections are unchanged.
+    #[must_use]
+    pub fn changed(&self) -> Range<usize> {
+        self.changed.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_with_spans, ParseError};
+
+    use super::{Incremental, TextEdit};
+
+    fn edit(source: &str, range: std::ops::Range<usize>, replacement: &str) -> (String, TextEdit) {
+        let mut edited = source.to_owned();
+        edited.replace_range(range.clone(), replacement);
+
+        (
+            edited,
+            TextEdit {
+                range,
+                replacement: replacement.to_owned(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_reparse() {
+        let source =
+            "<!-- a -->\n<template>\n  <p>a</p>\n</template>\n\n<style lang=\"css\">a {}</style>\n";
+        let parsed = Incremental::parse(source).unwrap();
+
+        assert_eq!(parsed.changed(), 0..3);
+
+        // Within the template content.
+        let (edited, text_edit) = edit(source, 27..28, "b <template>c</template>");
+        let reparsed = parsed.reparse(&edited, &text_edit).unwrap();
+
+        assert_eq!(reparsed.changed(), 1..2);
+        assert_eq!(
+            reparsed.spanned_sections(),
+            parse_with_spans(&edited).unwrap()
+        );
+
+        // Within the style content, the template is shifted.
+        let (edited, text_edit) = edit(source, 68..68, "b {}\n");
+        let reparsed = parsed.reparse(&edited, &text_edit).unwrap();
+
+        assert_eq!(reparsed.changed(), 2..3);
+        assert_eq!(
+            reparsed.spanned_sections(),
+            parse_with_spans(&edited).unwrap()
+        );
+
+        // Within a start tag.
+        let (edited, text_edit) = edit(source, 52..63, "");
+        let reparsed = parsed.reparse(&edited, &text_edit).unwrap();
+
+        assert_eq!(reparsed.changed(), 0..3);
+        assert_eq!(
+            reparsed.spanned_sections(),
+            parse_with_spans(&edited).unwrap()
+        );
+
+        // Within a raw section.
+        let (edited, text_edit) = edit(source, 5..5, "<i18n></i18n>");
+        let reparsed = parsed.reparse(&edited, &text_edit).unwrap();
+
+        assert_eq!(reparsed.changed(), 0..5);
+        assert_eq!(
+            reparsed.spanned_sections(),
+            parse_with_spans(&edited).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reparse_boundaries() {
+        let source = "<script>a</script>\n<style></style>";
+        let parsed = Incremental::parse(source).unwrap();
+
+        // Closes the block early.
+        let (edited, text_edit) = edit(source, 8..8, "</script><script>");
+        let reparsed = parsed.reparse(&edited, &text_edit).unwrap();
+
+        assert_eq!(reparsed.changed(), 0..3);
+        assert_eq!(
+            reparsed.spanned_sections(),
+            parse_with_spans(&edited).unwrap()
+        );
+
+        let (edited, text_edit) = edit(source, 8..8, "</script>");
+
+        assert!(matches!(
+            parsed.reparse(&edited, &text_edit),
+            Err(ParseError::UnexpectedEndTag(name, 18)) if name == "script"
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+/// A line ending style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum LineEnding {
+    /// `\n`.
+    #[default]
+    Lf,
+    /// `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns the dominant line ending of the given strings, [`LineEnding::Lf`] on ties.
+    ///
+    /// # Example
+    /// ```rust
+    /// use vue_sfc::parser::LineEnding;
+    ///
+    /// assert_eq!(LineEnding::detect(["a\r\nb\r\nc\n"]), LineEnding::CrLf);
+    /// assert_eq!(LineEnding::detect(["a"]), LineEnding::Lf);
+    /// ```
+    pub fn detect<'s>(strs: impl IntoIterator<Item = &'s str>) -> Self {
+        let (mut lf, mut crlf) = (0_usize, 0_usize);
+
+        for str in strs {
+            let newlines = memchr::memchr_iter(b'\n', str.as_bytes()).count();
+            let carriage_returns = memchr::memmem::find_iter(str.as_bytes(), "\r\n").count();
+
+            lf += newlines - carriage_returns;
+            crlf += carriage_returns;
+        }
+
+        if crlf > lf {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+
+    /// Replace every line ending of `input` with `self`.
+    #[must_use]
+    pub fn normalize(self, input: &str) -> Cow<'_, str> {
+        let is_normalized = match self {
+            Self::Lf => !input.contains("\r\n"),
+            Self::CrLf => {
+                memchr::memchr_iter(b'\n', input.as_bytes()).count()
+                    == memchr::memmem::find_iter(input.as_bytes(), "\r\n").count()
+            }
+        };
+
+        if is_normalized {
+            return Cow::Borrowed(input);
+        }
+
+        let lf = input.replace("\r\n", "\n");
+
+        Cow::Owned(match self {
+            Self::Lf => lf,
+            Self::CrLf => lf.replace('\n', "\r\n"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineEnding;
+
+    #[test]
+    fn test_normalize() {
+        let input = "a\r\nb\nc\r\n";
+
+        assert_eq!(LineEnding::detect([input]), LineEnding::CrLf);
+        assert_eq!(LineEnding::Lf.normalize(input), "a\nb\nc\n");
+        assert_eq!(LineEnding::CrLf.normalize(input), "a\r\nb\r\nc\r\n");
+        assert_eq!(LineEnding::CrLf.normalize("a\r\nb"), "a\r\nb");
+    }
+}
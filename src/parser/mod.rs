@@ -1,13 +1,23 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, iter::FusedIterator, ops::Range};
 
-pub use self::error::ParseError;
-use self::util::{parse_start_tag, trim_start_newlines_end};
-
-use crate::{
-    parser::util::parse_end_tag, Attribute, AttributeValue, Block, BlockName, Raw, Section,
+pub use self::error::{ParseError, ParseWarning};
+pub use self::incremental::{Incremental, TextEdit};
+pub use self::line_ending::LineEnding;
+pub use self::options::{
+    ContentMode, ContentModeHook, ContentModeRule, DuplicateAttributes, ParseOptions,
 };
+pub use self::source::{DecodeError, Encoding, Source};
+pub use self::tokenizer::{Token, Tokenizer};
+use self::util::{parse_start_tag, trim_start_newlines_end_range, StartTag};
+
+use crate::{parser::util::parse_end_tag, Attribute, Block, BlockName, Raw, Section};
 
 mod error;
+mod incremental;
+mod line_ending;
+mod options;
+mod source;
+mod tokenizer;
 mod util;
 
 /// Represent the state of the parser.
@@ -20,6 +30,7 @@ enum State<'a> {
     Data {
         name: BlockName<'a>,
         attributes: Vec<Attribute<'a>>,
+        span: SectionSpan,
         depth: u16,
     },
     /// When the parser is in a block in `RAWTEXT state`.
@@ -27,11 +38,32 @@ enum State<'a> {
     RawText {
         name: BlockName<'a>,
         attributes: Vec<Attribute<'a>>,
+        span: SectionSpan,
+        /// Indentation of the start tag, see [`ContentMode::Indented`].
+        indent: Option<usize>,
     },
+    /// When the input was fully parsed, or parsing failed.
+    Done,
+}
+
+/// Byte ranges covered by a parsed section in the input.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct SectionSpan {
+    /// The whole section, from the start tag's `<` to the end tag's `>` for blocks.
+    pub outer: Range<usize>,
+    /// The (trimmed) content of the section.
+    pub content: Range<usize>,
+    /// The start tag of a block, empty for raw sections.
+    pub start_tag: Range<usize>,
+    /// Each attribute of a block, empty for raw sections.
+    pub attributes: Vec<Range<usize>>,
 }
 
 /// Parse the given input as a Vue SFC.
 ///
+/// A leading byte order mark (`U+FEFF`) is skipped, see [`Source`] to parse bytes.
+///
 /// # Errors
 /// Will return an error if parsing fails.
 ///
@@ -61,136 +93,501 @@ enum State<'a> {
 /// }
 /// ```
 pub fn parse(input: &str) -> Result<Vec<Section<'_>>, ParseError> {
-    let mut less_than_symbols = memchr::memmem::find_iter(input.as_bytes(), "<");
-
-    let mut buffer = Vec::new();
-    let mut offset = 0;
-    let mut state = State::Root;
-
-    loop {
-        match state {
-            State::Root => {
-                let index = if let Some(index) = less_than_symbols.next() {
-                    index
-                } else {
-                    let raw = trim_start_newlines_end(&input[offset..]);
-
-                    if !raw.is_empty() {
-                        // SAFETY: `raw` is end-trimmed and non-empty.
-                        let raw = unsafe { Raw::from_cow_unchecked(Cow::Borrowed(raw)) };
-                        buffer.push(Section::Raw(raw));
-                    }
+    Sections::new(input).collect()
+}
 
-                    break;
-                };
+/// The result of [`parse_with_options`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Parsed<'a> {
+    pub sections: Vec<Section<'a>>,
+    /// Recoverable errors, in order of occurrence.
+    pub warnings: Vec<ParseWarning>,
+    /// The dominant line ending of the input.
+    pub line_ending: LineEnding,
+}
 
-                if let Ok((_, name)) = parse_end_tag(&input[index..]) {
-                    return Err(ParseError::UnexpectedEndTag(name.as_str().to_owned()));
-                }
+/// Same as [`parse`], with the given options.
+///
+/// # Errors
+/// Will return an error if parsing fails.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::parser::{parse_with_options, DuplicateAttributes, ParseOptions};
+/// use vue_sfc::Section;
+///
+/// let options = ParseOptions {
+///     duplicate_attributes: DuplicateAttributes::Warn,
+///     ..ParseOptions::default()
+/// };
+///
+/// let parsed = parse_with_options("<script lang=\"ts\" lang=\"js\"></script>", &options).unwrap();
+///
+/// assert_eq!(parsed.warnings.len(), 1);
+///
+/// match &parsed.sections[0] {
+///     Section::Block(block) => assert_eq!(block.attributes.len(), 1),
+///     Section::Raw(_) => unreachable!(),
+/// }
+/// ```
+pub fn parse_with_options<'a>(
+    input: &'a str,
+    options: &ParseOptions,
+) -> Result<Parsed<'a>, ParseError> {
+    let mut iter = Sections::with_options(input, options.clone());
+    let sections = iter.by_ref().collect::<Result<_, _>>()?;
+
+    Ok(Parsed {
+        sections,
+        warnings: iter.warnings,
+        line_ending: LineEnding::detect([input]),
+    })
+}
 
-                if let Ok((remaining, (name, attributes))) = parse_start_tag(&input[index..]) {
-                    let raw = trim_start_newlines_end(&input[offset..index]);
+/// Same as [`parse`], but also returns the span of each section.
+pub(crate) fn parse_with_spans(input: &str) -> Result<Vec<(Section<'_>, SectionSpan)>, ParseError> {
+    let mut iter = Sections::new(input);
 
-                    if !raw.is_empty() {
-                        // SAFETY: `raw` is end-trimmed and non-empty.
-                        let raw = unsafe { Raw::from_cow_unchecked(Cow::Borrowed(raw)) };
-                        buffer.push(Section::Raw(raw));
-                    }
+    std::iter::from_fn(|| iter.next_spanned().transpose()).collect()
+}
+
+/// A lazy iterator over the sections of a Vue SFC, see [`Sections::new`].
+///
+/// Once an error is returned, the iterator only returns `None`.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::{parser::Sections, Section};
+///
+/// let input = "<style></style><script></script><template></template>";
+///
+/// // Stops at the first `<style>` block, the rest of the input is never scanned.
+/// let has_style = Sections::new(input)
+///     .map_while(Result::ok)
+///     .any(|section| matches!(section, Section::Block(block) if block.name == "style"));
+///
+/// assert!(has_style);
+/// ```
+#[derive(Debug)]
+pub struct Sections<'a> {
+    input: &'a str,
+    options: ParseOptions,
+    warnings: Vec<ParseWarning>,
+    less_than_symbols: memchr::memmem::FindIter<'a, 'static>,
+    offset: usize,
+    state: State<'a>,
+    /// A self-closing block found along with the raw section preceding it.
+    pending: Option<(Section<'a>, SectionSpan)>,
+}
 
-                    let raw_text = name.as_str() != "template"
-                        || attributes.iter().any(|(name, value)| {
-                            matches!(
-                                (name.as_str(), value.as_ref().map(AttributeValue::as_str)),
-                                ("lang", Some(lang)) if lang != "html"
-                            )
-                        });
-
-                    offset = input.len() - remaining.len();
-                    state = if raw_text {
-                        State::RawText { name, attributes }
+impl<'a> Sections<'a> {
+    /// Returns an iterator over the sections of `input`, parsed as a Vue SFC.
+    ///
+    /// A leading byte order mark (`U+FEFF`) is skipped, see [`Source`] to parse bytes.
+    pub fn new(input: &'a str) -> Self {
+        Self::with_options(input, ParseOptions::default())
+    }
+
+    /// Same as [`Sections::new`], with the given options.
+    pub fn with_options(input: &'a str, options: ParseOptions) -> Self {
+        // Skip the byte order mark.
+        let start = if input.starts_with('\u{FEFF}') {
+            '\u{FEFF}'.len_utf8()
+        } else {
+            0
+        };
+
+        Self::starting_at(input, start, options)
+    }
+
+    /// Parse `input` from `start`, which must be at root level.
+    pub(crate) fn starting_at(input: &'a str, start: usize, options: ParseOptions) -> Self {
+        Self {
+            input,
+            options,
+            warnings: Vec::new(),
+            less_than_symbols: memchr::memmem::find_iter(input.as_bytes(), "<"),
+            offset: start,
+            state: State::Root,
+            pending: None,
+        }
+    }
+
+    /// Recoverable errors found so far, in order of occurrence.
+    #[must_use]
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Returns the next section along with its span.
+    pub(crate) fn next_spanned(
+        &mut self,
+    ) -> Result<Option<(Section<'a>, SectionSpan)>, ParseError> {
+        if let Some(pending) = self.pending.take() {
+            return Ok(Some(pending));
+        }
+
+        let result = self.scan();
+
+        if result.is_err() {
+            self.state = State::Done;
+        }
+
+        result
+    }
+
+    fn scan(&mut self) -> Result<Option<(Section<'a>, SectionSpan)>, ParseError> {
+        let input = self.input;
+
+        loop {
+            match self.state {
+                State::Done => return Ok(None),
+                State::Root => {
+                    let index = if let Some(index) = self.less_than_symbols.next() {
+                        index
                     } else {
-                        State::Data {
+                        self.state = State::Done;
+
+                        return Ok(raw_section(input, self.offset..input.len()));
+                    };
+
+                    if index < self.offset {
+                        // Before the start, or inside the start tag of the previous
+                        // (self-closing) block.
+                        continue;
+                    }
+
+                    if let Ok((_, name)) = parse_end_tag(&input[index..]) {
+                        return Err(ParseError::UnexpectedEndTag(
+                            name.as_str().to_owned(),
+                            index,
+                        ));
+                    }
+
+                    if let Ok((remaining, mut start_tag)) = parse_start_tag(&input[index..]) {
+                        handle_duplicate_attributes(
+                            &mut start_tag,
+                            index,
+                            &self.options,
+                            &mut self.warnings,
+                        )?;
+
+                        let StartTag {
                             name,
                             attributes,
-                            depth: 0,
+                            attribute_spans,
+                            self_closing,
+                        } = start_tag;
+
+                        let raw = raw_section(input, self.offset..index);
+
+                        self.offset = input.len() - remaining.len();
+
+                        let span = SectionSpan {
+                            outer: index..self.offset,
+                            content: self.offset..self.offset,
+                            start_tag: index..self.offset,
+                            attributes: attribute_spans
+                                .into_iter()
+                                .map(|span| index + span.start..index + span.end)
+                                .collect(),
+                        };
+
+                        if self_closing {
+                            let block = (
+                                Section::Block(Block {
+                                    name,
+                                    attributes,
+                                    content: Cow::Borrowed(""),
+                                }),
+                                span,
+                            );
+
+                            return Ok(Some(match raw {
+                                Some(raw) => {
+                                    self.pending = Some(block);
+                                    raw
+                                }
+                                None => block,
+                            }));
                         }
-                    };
-                }
-            }
-            State::Data {
-                name: ref parent_name,
-                ref mut attributes,
-                ref mut depth,
-            } => {
-                let index = less_than_symbols
-                    .next()
-                    .ok_or_else(|| ParseError::MissingEndTag(parent_name.as_str().to_owned()))?;
-
-                match parse_end_tag(&input[index..]) {
-                    Ok((remaining, name)) if &name == parent_name => {
-                        if *depth == 0 {
-                            buffer.push(Section::Block(Block {
+
+                        self.state = match self.options.content_mode(&name, &attributes) {
+                            ContentMode::RawText => State::RawText {
                                 name,
-                                attributes: std::mem::take(attributes),
-                                content: Cow::Borrowed(trim_start_newlines_end(
-                                    &input[offset..index],
-                                )),
-                            }));
+                                attributes,
+                                span,
+                                indent: None,
+                            },
+                            ContentMode::Indented => State::RawText {
+                                name,
+                                attributes,
+                                span,
+                                indent: Some(index - line_start(input, index)),
+                            },
+                            ContentMode::Data => State::Data {
+                                name,
+                                attributes,
+                                span,
+                                depth: 0,
+                            },
+                        };
+
+                        if raw.is_some() {
+                            return Ok(raw);
+                        }
+                    }
+                }
+                State::Data {
+                    name: ref mut parent_name,
+                    ref mut attributes,
+                    ref mut span,
+                    ref mut depth,
+                } => {
+                    let index = self.less_than_symbols.next().ok_or_else(|| {
+                        ParseError::MissingEndTag(parent_name.as_str().to_owned(), span.outer.start)
+                    })?;
+
+                    if index < self.offset {
+                        // Inside the start tag of the block.
+                        continue;
+                    }
+
+                    match parse_end_tag(&input[index..]) {
+                        Ok((remaining, name)) if name == *parent_name => {
+                            if *depth == 0 {
+                                let end = input.len() - remaining.len();
+                                let content =
+                                    trim_start_newlines_end_range(input, self.offset..index);
+
+                                let block = (
+                                    Section::Block(Block {
+                                        // Keep the casing of the start tag.
+                                        name: std::mem::take(parent_name),
+                                        attributes: std::mem::take(attributes),
+                                        content: Cow::Borrowed(&input[content.clone()]),
+                                    }),
+                                    SectionSpan {
+                                        outer: span.outer.start..end,
+                                        content,
+                                        ..std::mem::take(span)
+                                    },
+                                );
+
+                                self.offset = end;
+                                self.state = State::Root;
+
+                                return Ok(Some(block));
+                            }
 
-                            offset = input.len() - remaining.len();
-                            state = State::Root;
-                        } else {
                             *depth -= 1;
+
+                            // Skip start tag check.
+                            continue;
                         }
+                        _ => { /* Ignore parsing failure & non-matching end tag. */ }
+                    }
 
-                        // Skip start tag check.
-                        continue;
+                    match parse_start_tag(&input[index..]) {
+                        Ok((
+                            _,
+                            StartTag {
+                                name,
+                                self_closing: false,
+                                ..
+                            },
+                        )) if name == *parent_name => {
+                            *depth += 1;
+                        }
+                        _ => { /* Ignore parsing failure & non-matching start tag. */ }
                     }
-                    _ => { /* Ignore parsing failure & non-matching end tag. */ }
                 }
+                State::RawText {
+                    name: ref mut parent_name,
+                    ref mut attributes,
+                    ref mut span,
+                    indent,
+                } => {
+                    let index = self.less_than_symbols.next().ok_or_else(|| {
+                        ParseError::MissingEndTag(parent_name.as_str().to_owned(), span.outer.start)
+                    })?;
+
+                    if index < self.offset {
+                        // Inside the start tag of the block.
+                        continue;
+                    }
 
-                match parse_start_tag(&input[index..]) {
-                    Ok((_, (name, _))) if &name == parent_name => {
-                        *depth += 1;
+                    match parse_end_tag(&input[index..]) {
+                        Ok((remaining, name))
+                            if name == *parent_name
+                                && indent.map_or(true, |indent| {
+                                    is_indented_end_tag(input, self.offset, index, indent)
+                                }) =>
+                        {
+                            let end = input.len() - remaining.len();
+                            let content = trim_start_newlines_end_range(input, self.offset..index);
+
+                            let block = (
+                                Section::Block(Block {
+                                    // Keep the casing of the start tag.
+                                    name: std::mem::take(parent_name),
+                                    attributes: std::mem::take(attributes),
+                                    content: Cow::Borrowed(&input[content.clone()]),
+                                }),
+                                SectionSpan {
+                                    outer: span.outer.start..end,
+                                    content,
+                                    ..std::mem::take(span)
+                                },
+                            );
+
+                            self.offset = end;
+                            self.state = State::Root;
+
+                            return Ok(Some(block));
+                        }
+                        _ => { /* Ignore non-matching end tags. */ }
                     }
-                    _ => { /* Ignore parsing failure & non-matching start tag. */ }
                 }
             }
-            State::RawText {
-                name: ref parent_name,
-                ref mut attributes,
-            } => {
-                let index = less_than_symbols
-                    .next()
-                    .ok_or_else(|| ParseError::MissingEndTag(parent_name.as_str().to_owned()))?;
-
-                match parse_end_tag(&input[index..]) {
-                    Ok((remaining, name)) if &name == parent_name => {
-                        buffer.push(Section::Block(Block {
-                            name,
-                            attributes: std::mem::take(attributes),
-                            content: Cow::Borrowed(trim_start_newlines_end(&input[offset..index])),
-                        }));
+        }
+    }
+}
 
-                        offset = input.len() - remaining.len();
-                        state = State::Root;
-                    }
-                    _ => { /* Ignore non-matching end tags. */ }
-                }
+impl<'a> Iterator for Sections<'a> {
+    type Item = Result<Section<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_spanned()
+            .map(|section| section.map(|(section, _)| section))
+            .transpose()
+    }
+}
+
+impl FusedIterator for Sections<'_> {}
+
+/// Returns the offset of the start of the line containing `index`.
+fn line_start(input: &str, index: usize) -> usize {
+    memchr::memrchr(b'\n', &input.as_bytes()[..index]).map_or(0, |newline| newline + 1)
+}
+
+/// Whether the end tag at `index` closes a block in [`ContentMode::Indented`], whose start tag
+/// ends at `content_start` and is indented by `indent` bytes.
+fn is_indented_end_tag(input: &str, content_start: usize, index: usize, indent: usize) -> bool {
+    let line_start = line_start(input, index);
+
+    // On the same line as the start tag.
+    if line_start <= content_start {
+        return true;
+    }
+
+    index - line_start <= indent
+        && input[line_start..index]
+            .bytes()
+            .all(|byte| matches!(byte, b' ' | b'\t'))
+}
+
+/// Apply [`ParseOptions::duplicate_attributes`] to a root level start tag found at `offset`.
+fn handle_duplicate_attributes(
+    start_tag: &mut StartTag,
+    offset: usize,
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<(), ParseError> {
+    if options.duplicate_attributes == DuplicateAttributes::Keep {
+        return Ok(());
+    }
+
+    let attributes = std::mem::take(&mut start_tag.attributes);
+    let attribute_spans = std::mem::take(&mut start_tag.attribute_spans);
+
+    for ((name, value), span) in attributes.into_iter().zip(attribute_spans) {
+        let is_duplicate = start_tag.attributes.iter().any(|(other, _)| *other == name);
+
+        if is_duplicate {
+            let name = name.as_str().to_owned();
+            let offset = offset + span.start;
+
+            if options.duplicate_attributes == DuplicateAttributes::Error {
+                return Err(ParseError::DuplicateAttribute(name, offset));
             }
+
+            warnings.push(ParseWarning::DuplicateAttribute(name, offset));
+        } else {
+            start_tag.attributes.push((name, value));
+            start_tag.attribute_spans.push(span);
         }
     }
 
-    Ok(buffer)
+    Ok(())
+}
+
+/// Rebuild the section of `input` covered by `span`, without re-scanning its content.
+///
+/// Returns `None` if `span` isn't the span of a section of `input`.
+pub(crate) fn section_from_span<'a>(
+    input: &'a str,
+    span: &SectionSpan,
+    options: &ParseOptions,
+    warnings: &mut Vec<ParseWarning>,
+) -> Result<Option<Section<'a>>, ParseError> {
+    let Some(content) = input.get(span.content.clone()) else {
+        return Ok(None);
+    };
+
+    // Raw sections have an empty start tag.
+    if span.start_tag.is_empty() {
+        return Ok(Raw::from_parsed(Cow::Borrowed(content))
+            .ok()
+            .map(Section::Raw));
+    }
+
+    let Some(Ok((_, mut start_tag))) = input.get(span.start_tag.clone()).map(parse_start_tag)
+    else {
+        return Ok(None);
+    };
+
+    handle_duplicate_attributes(&mut start_tag, span.start_tag.start, options, warnings)?;
+
+    Ok(Some(Section::Block(Block {
+        name: start_tag.name,
+        attributes: start_tag.attributes,
+        content: Cow::Borrowed(content),
+    })))
+}
+
+/// Build a raw section from the given range of `input`, unless it is empty once trimmed.
+fn raw_section(input: &str, range: Range<usize>) -> Option<(Section<'_>, SectionSpan)> {
+    let content = trim_start_newlines_end_range(input, range);
+
+    if content.is_empty() {
+        return None;
+    }
+
+    // `content` is trimmed and non-empty, but may contain a `<` which did not start a tag.
+    let raw = Raw::from_parsed(Cow::Borrowed(&input[content.clone()])).ok()?;
+
+    Some((
+        Section::Raw(raw),
+        SectionSpan {
+            outer: content.clone(),
+            content,
+            ..SectionSpan::default()
+        },
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::borrow::Cow;
+    use std::{borrow::Cow, sync::Arc};
 
-    use crate::{Block, BlockName, Raw, Section};
+    use crate::{Attribute, Block, BlockName, Raw, Section};
 
-    use super::parse;
+    use super::{
+        parse, parse_with_options, ContentMode, ContentModeRule, DuplicateAttributes, ParseError,
+        ParseOptions, Sections,
+    };
 
     #[test]
     fn test_parse_empty() {
@@ -205,6 +602,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_tag_in_start_tag() {
+        for (input, content) in [
+            ("<template title=\"</template>\"></template>", ""),
+            ("<template title=\"<template>\">a</template>", "a"),
+            ("<script title=\"</script>\">a</script>", "a"),
+        ] {
+            let sections = parse(input).unwrap();
+
+            assert!(
+                matches!(sections.as_slice(), [Section::Block(block)] if block.content == content),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_bom() {
+        assert_eq!(parse("\u{FEFF}").unwrap(), vec![]);
+        assert_eq!(
+            parse("\u{FEFF}<!-- a comment -->").unwrap(),
+            vec![Section::Raw(Raw::try_from("<!-- a comment -->").unwrap())]
+        );
+    }
+
     #[test]
     fn test_parse_block() {
         assert_eq!(
@@ -217,6 +639,206 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let err = parse("<!-- -->\n<script>").unwrap_err();
+        assert!(matches!(err, ParseError::MissingEndTag(_, _)));
+        assert_eq!(err.offset(), 9);
+
+        let err = parse("<template></template>\n</script>").unwrap_err();
+        assert!(matches!(err, ParseError::UnexpectedEndTag(_, _)));
+        assert_eq!(err.offset(), 22);
+    }
+
+    #[test]
+    fn test_sections() {
+        let mut sections = Sections::new("<!-- a -->\n<style src=\"./a.css\" />\n</script>");
+
+        assert!(
+            matches!(sections.next(), Some(Ok(Section::Raw(raw))) if raw.as_str() == "<!-- a -->")
+        );
+        assert!(
+            matches!(sections.next(), Some(Ok(Section::Block(block))) if block.name == "style")
+        );
+        assert!(matches!(
+            sections.next(),
+            Some(Err(ParseError::UnexpectedEndTag(_, 35)))
+        ));
+        assert!(sections.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_duplicate_attributes() {
+        let input = "<script lang=\"ts\" setup LANG=\"js\"></script>";
+
+        let parsed = parse_with_options(input, &ParseOptions::default()).unwrap();
+        assert!(parsed.warnings.is_empty());
+        assert!(
+            matches!(&parsed.sections[0], Section::Block(block) if block.attributes.len() == 3)
+        );
+
+        let err = parse_with_options(
+            input,
+            &ParseOptions {
+                duplicate_attributes: DuplicateAttributes::Error,
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ParseError::DuplicateAttribute(_, 24)));
+
+        let parsed = parse_with_options(
+            input,
+            &ParseOptions {
+                duplicate_attributes: DuplicateAttributes::Warn,
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(parsed.warnings.len(), 1);
+        assert_eq!(parsed.warnings[0].offset(), 24);
+        assert_eq!(
+            parsed.sections[0].to_string(),
+            "<script lang=\"ts\" setup></script>"
+        );
+    }
+
+    #[test]
+    fn test_parse_character_references() {
+        let input = "<i18n locale=\"en&amp;us\" title='&#x22;a&quot;'></i18n>";
+
+        match &parse(input).unwrap()[0] {
+            Section::Block(block) => {
+                let locale = block.attributes[0].1.as_ref().unwrap();
+                assert_eq!(locale.as_str(), "en&us");
+                assert_eq!(locale.raw(), "en&amp;us");
+
+                let title = block.attributes[1].1.as_ref().unwrap();
+                assert_eq!(title.as_str(), "\"a\"");
+
+                let printed = block.to_string();
+                assert_eq!(
+                    printed,
+                    "<i18n locale=\"en&amp;us\" title=\"&#x22;a&quot;\"></i18n>"
+                );
+                assert_eq!(parse(&printed).unwrap()[0], Section::Block(block.clone()));
+            }
+            Section::Raw(_) => panic!("expected a block"),
+        }
+    }
+
+    #[test]
+    fn test_parse_self_closing() {
+        let input = concat!(
+            "<script src=\"./a.ts\" title=\"<b>\" />\n",
+            "<style src='./a.css'/>\n",
+            "<template><template/><div/></template>"
+        );
+
+        let sections = parse(input).unwrap();
+        assert_eq!(sections.len(), 3);
+
+        match &sections[0] {
+            Section::Block(block) => {
+                assert_eq!(block.name.as_str(), "script");
+                assert_eq!(block.attributes.len(), 2);
+                assert!(block.content.is_empty());
+            }
+            Section::Raw(_) => panic!("expected a block"),
+        }
+
+        match &sections[2] {
+            Section::Block(block) => assert_eq!(block.content, "<template/><div/>"),
+            Section::Raw(_) => panic!("expected a block"),
+        }
+    }
+
+    #[test]
+    fn test_parse_case() {
+        let input = "<I18n LANG=\"json\">{}</i18n>\n<Docs></DOCS>";
+        let sections = parse(input).unwrap();
+
+        match &sections[0] {
+            Section::Block(block) => {
+                assert_eq!(block.name.as_str(), "I18n");
+                assert_eq!(block.name, "i18n");
+                assert_eq!(block.name, BlockName::try_from("I18N").unwrap());
+                assert_eq!(block.attribute_value("lang").unwrap().as_str(), "json");
+                assert_eq!(block.to_string(), "<I18n LANG=\"json\">\n{}\n</I18n>");
+            }
+            Section::Raw(_) => panic!("expected a block"),
+        }
+
+        let names = sections
+            .iter()
+            .filter_map(|section| match section {
+                Section::Block(block) => Some(block.name.clone()),
+                Section::Raw(_) => None,
+            })
+            .chain([BlockName::try_from("docs").unwrap()])
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_content_modes() {
+        let input = "<docs lang=\"md\"><docs>a</docs></docs><demo><demo></demo></demo>";
+
+        let options = ParseOptions {
+            content_modes: vec![ContentModeRule {
+                name: String::from("docs"),
+                lang: Some(String::from("md")),
+                mode: ContentMode::Data,
+            }],
+            content_mode_hook: Some(Arc::new(|name: &BlockName, _: &[Attribute]| {
+                (*name == "demo").then_some(ContentMode::Data)
+            })),
+            ..ParseOptions::default()
+        };
+
+        let contents = parse_with_options(input, &options)
+            .unwrap()
+            .sections
+            .into_iter()
+            .map(|section| match section {
+                Section::Block(block) => block.content,
+                Section::Raw(_) => panic!("expected a block"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(contents, vec!["<docs>a</docs>", "<demo></demo>"]);
+
+        // Without options, both blocks end at the first end tag.
+        assert!(parse(input).is_err());
+    }
+
+    #[test]
+    fn test_parse_indented() {
+        let input = concat!(
+            "<template lang=\"pug\">\n",
+            "div\n",
+            "  p </template>\n",
+            "  | </template>\n",
+            "</template>\n",
+            "<template lang=\"pug\">p</template>",
+        );
+
+        let sections = parse(input).unwrap();
+
+        match &sections[0] {
+            Section::Block(block) => {
+                assert_eq!(block.content, "div\n  p </template>\n  | </template>")
+            }
+            Section::Raw(_) => panic!("expected a block"),
+        }
+
+        match &sections[1] {
+            Section::Block(block) => assert_eq!(block.content, "p"),
+            Section::Raw(_) => panic!("expected a block"),
+        }
+    }
+
     #[test]
     fn test_parse_consecutive_blocks() {
         assert_eq!(
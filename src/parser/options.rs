@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use crate::{Attribute, BlockName};
+
+/// Decides the [`ContentMode`] of a block from its name and attributes, see
+/// [`ParseOptions::content_mode_hook`].
+pub type ContentModeHook =
+    Arc<dyn Fn(&BlockName, &[Attribute]) -> Option<ContentMode> + Send + Sync>;
+
+/// Options for [`parse_with_options`][super::parse_with_options].
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    /// Defaults to [`DuplicateAttributes::Keep`].
+    pub duplicate_attributes: DuplicateAttributes,
+    /// Content mode of blocks, by name & `lang` attribute. The first matching rule is used.
+    /// Defaults to an empty list.
+    pub content_modes: Vec<ContentModeRule>,
+    /// Called for each block before [`ParseOptions::content_modes`], returning `None` falls back
+    /// to the rules. Defaults to `None`.
+    pub content_mode_hook: Option<ContentModeHook>,
+}
+
+impl ParseOptions {
+    /// Content mode of a block, see [`ContentMode`] for the default.
+    #[must_use]
+    pub fn content_mode(&self, name: &BlockName, attributes: &[Attribute]) -> ContentMode {
+        if let Some(mode) = self
+            .content_mode_hook
+            .as_ref()
+            .and_then(|hook| hook(name, attributes))
+        {
+            return mode;
+        }
+
+        let lang = attributes
+            .iter()
+            .find(|(name, _)| name == "lang")
+            .and_then(|(_, value)| value.as_ref())
+            .map(|value| value.as_str());
+
+        if let Some(rule) = self
+            .content_modes
+            .iter()
+            .find(|rule| rule.matches(name, lang))
+        {
+            return rule.mode;
+        }
+
+        match lang {
+            _ if *name != "template" => ContentMode::RawText,
+            None | Some("html") => ContentMode::Data,
+            Some("pug" | "jade") => ContentMode::Indented,
+            Some(_) => ContentMode::RawText,
+        }
+    }
+}
+
+impl std::fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("duplicate_attributes", &self.duplicate_attributes)
+            .field("content_modes", &self.content_modes)
+            .field(
+                "content_mode_hook",
+                &self.content_mode_hook.as_ref().map(|_| ".."),
+            )
+            .finish()
+    }
+}
+
+/// How to handle a block start tag with several attributes of the same name
+/// (compared ASCII case-insensitively), i.e: `<script lang="ts" lang="js">`.
+///
+/// See <https://html.spec.whatwg.org/multipage/parsing.html#attribute-name-state>.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DuplicateAttributes {
+    /// Keep every attribute, in order.
+    #[default]
+    Keep,
+    /// Fail with [`ParseError::DuplicateAttribute`][super::ParseError::DuplicateAttribute].
+    Error,
+    /// As per the HTML specification, keep the first attribute and drop the later ones, reporting
+    /// a [`ParseWarning::DuplicateAttribute`][super::ParseWarning::DuplicateAttribute] for each.
+    Warn,
+}
+
+/// How the content of a block is parsed.
+///
+/// By default, `template` blocks without `lang` attribute (or with `lang="html"`) are parsed in
+/// [`ContentMode::Data`], `template` blocks with `lang="pug"` (or `lang="jade"`) in
+/// [`ContentMode::Indented`], other blocks in [`ContentMode::RawText`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ContentMode {
+    /// The block ends at the first matching end tag.
+    /// See <https://html.spec.whatwg.org/multipage/parsing.html#rawtext-state>.
+    RawText,
+    /// Nested tags with the same name as the block are accounted for, i.e:
+    /// `<template><template #default></template></template>`.
+    /// See <https://html.spec.whatwg.org/multipage/parsing.html#data-state>.
+    Data,
+    /// Same as [`ContentMode::RawText`], but the end tag is only recognized on the line of the
+    /// start tag, or at the start of a line indented no more than the start tag. Meant for
+    /// indentation-based languages such as pug, where `p </template>` is text.
+    Indented,
+}
+
+/// See [`ParseOptions::content_modes`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContentModeRule {
+    /// Name of the block (compared ASCII case-insensitively).
+    pub name: String,
+    /// Value of the `lang` attribute:
+    /// - `None` matches any block,
+    /// - `Some("")` matches blocks without `lang` attribute,
+    /// - `Some(lang)` matches blocks with the given `lang` attribute.
+    pub lang: Option<String>,
+    pub mode: ContentMode,
+}
+
+impl ContentModeRule {
+    fn matches(&self, name: &BlockName, lang: Option<&str>) -> bool {
+        *name == self.name.as_str()
+            && match self.lang.as_deref() {
+                None => true,
+                Some("") => lang.is_none(),
+                Some(expected) => lang == Some(expected),
+            }
+    }
+}
@@ -0,0 +1,143 @@
+use std::{borrow::Cow, str};
+
+use super::{parse, ParseError};
+use crate::Section;
+
+pub use self::error::DecodeError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    /// Returned when [`Source::decode`][super::Source::decode] fails.
+    #[derive(Debug)]
+    pub struct DecodeError {
+        pub(super) offset: usize,
+    }
+
+    impl DecodeError {
+        /// Byte offset of the first invalid sequence in the input (including the BOM, if any).
+        #[must_use]
+        pub fn offset(&self) -> usize {
+            self.offset
+        }
+    }
+
+    impl Display for DecodeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "invalid UTF-8 at byte {}", self.offset)
+        }
+    }
+
+    impl Error for DecodeError {}
+}
+
+/// UTF-8 byte order mark.
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Declared encoding of the input of [`Source::decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    /// ISO-8859-1, each byte is decoded to the code point of the same value.
+    Latin1,
+}
+
+/// A Vue SFC decoded from bytes, see [`Source::decode`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Source<'a> {
+    text: Cow<'a, str>,
+    bom: bool,
+}
+
+impl<'a> Source<'a> {
+    /// Decode `bytes` using the given encoding.
+    ///
+    /// A leading UTF-8 byte order mark is stripped (and recorded, see [`Source::has_bom`]), in
+    /// which case the input is decoded as UTF-8 regardless of `encoding`.
+    ///
+    /// # Errors
+    /// Will return an error if the input is decoded as UTF-8 but isn't valid UTF-8.
+    ///
+    /// # Example
+    /// ```rust
+    /// use vue_sfc::parser::{Encoding, Source};
+    ///
+    /// let source = Source::decode(b"\xEF\xBB\xBF<template></template>", Encoding::Utf8).unwrap();
+    ///
+    /// assert!(source.has_bom());
+    /// assert_eq!(source.as_str(), "<template></template>");
+    /// assert_eq!(source.parse().unwrap().len(), 1);
+    ///
+    /// let source = Source::decode(b"<i18n>caf\xE9</i18n>", Encoding::Latin1).unwrap();
+    ///
+    /// assert_eq!(source.as_str(), "<i18n>café</i18n>");
+    /// ```
+    pub fn decode(bytes: &'a [u8], encoding: Encoding) -> Result<Self, DecodeError> {
+        let (bytes, bom, encoding) = match bytes.strip_prefix(BOM) {
+            Some(bytes) => (bytes, true, Encoding::Utf8),
+            None => (bytes, false, encoding),
+        };
+
+        let text = match encoding {
+            Encoding::Utf8 => Cow::Borrowed(str::from_utf8(bytes).map_err(|err| DecodeError {
+                offset: err.valid_up_to() + if bom { BOM.len() } else { 0 },
+            })?),
+            Encoding::Latin1 if bytes.is_ascii() => {
+                // SAFETY: ASCII is valid UTF-8.
+                Cow::Borrowed(unsafe { str::from_utf8_unchecked(bytes) })
+            }
+            Encoding::Latin1 => Cow::Owned(bytes.iter().map(|&byte| char::from(byte)).collect()),
+        };
+
+        Ok(Self { text, bom })
+    }
+
+    /// The decoded text, without byte order mark.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether the input started with a byte order mark.
+    #[must_use]
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Parse the decoded text, see [`parse`][super::parse].
+    ///
+    /// Spans & offsets are relative to [`Source::as_str`].
+    ///
+    /// # Errors
+    /// Will return an error if parsing fails.
+    pub fn parse(&self) -> Result<Vec<Section<'_>>, ParseError> {
+        parse(self.as_str())
+    }
+
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.text.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Encoding, Source};
+
+    #[test]
+    fn test_decode() {
+        let source = Source::decode(b"<a>\xC3\xA9</a>", Encoding::Latin1).unwrap();
+        assert!(!source.has_bom());
+        assert_eq!(source.as_str(), "<a>\u{C3}\u{A9}</a>");
+
+        let source = Source::decode(b"\xEF\xBB\xBF<a>\xC3\xA9</a>", Encoding::Latin1).unwrap();
+        assert!(source.has_bom());
+        assert_eq!(source.as_str(), "<a>\u{E9}</a>");
+
+        let err = Source::decode(b"\xEF\xBB\xBF<a>\xC3</a>", Encoding::Utf8).unwrap_err();
+        assert_eq!(err.offset(), 6);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{iter::FusedIterator, ops::Range};
+
+use crate::{Attribute, BlockName};
+
+use super::util::{parse_end_tag, parse_start_tag, StartTag};
+
+/// A token, as returned by [`Tokenizer`].
+///
+/// Spans are byte ranges in the input, shifted by the offset given to
+/// [`Tokenizer::with_offset`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Token<'a> {
+    /// i.e: `<script lang="ts">` or `<img />`.
+    StartTag {
+        name: BlockName<'a>,
+        attributes: Vec<Attribute<'a>>,
+        /// Span of each attribute.
+        attribute_spans: Vec<Range<usize>>,
+        /// Whether the tag ends with `/>`.
+        self_closing: bool,
+        span: Range<usize>,
+    },
+    /// i.e: `</script>`.
+    EndTag {
+        name: BlockName<'a>,
+        span: Range<usize>,
+    },
+    /// Anything between tags & comments, character references are not decoded.
+    Text { text: &'a str, span: Range<usize> },
+    /// i.e: `<!-- a comment -->`, `text` is the comment without its delimiters.
+    Comment { text: &'a str, span: Range<usize> },
+}
+
+impl Token<'_> {
+    /// Byte range of the token.
+    #[must_use]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::StartTag { span, .. }
+            | Self::EndTag { span, .. }
+            | Self::Text { span, .. }
+            | Self::Comment { span, .. } => span.clone(),
+        }
+    }
+}
+
+/// A pull-based tokenizer, returning start tags, end tags, text & comments.
+///
+/// The tokenizer has no knowledge of the tags it returns: after the start tag of an element
+/// whose content is raw text (i.e: `<script>` or `<style>` at root level), call
+/// [`Tokenizer::enter_raw_text`] so its content is returned as text.
+///
+/// # Example
+/// ```rust
+/// use vue_sfc::parser::{Token, Tokenizer};
+///
+/// let mut tokenizer = Tokenizer::new("<template><p>a</p></template>\n<script>a<b</script>");
+/// let mut names = Vec::new();
+/// let mut depth = 0;
+///
+/// while let Some(token) = tokenizer.next() {
+///     match token {
+///         Token::StartTag { name, self_closing: false, .. } => {
+///             names.push(name.to_string());
+///
+///             if depth == 0 && name != "template" {
+///                 tokenizer.enter_raw_text();
+///             } else {
+///                 depth += 1;
+///             }
+///         }
+///         Token::EndTag { name, .. } if name != "script" => depth -= 1,
+///         _ => {}
+///     }
+/// }
+///
+/// assert_eq!(names, vec!["template", "p", "script"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tokenizer<'a> {
+    input: &'a str,
+    offset: usize,
+    position: usize,
+    /// A tag or comment found while looking for the end of a text token.
+    pending: Option<Token<'a>>,
+    /// Name of the last start tag, see [`Tokenizer::enter_raw_text`].
+    last_start_tag: Option<BlockName<'a>>,
+    /// Name of the element whose raw text content is to be returned next.
+    raw_text: Option<BlockName<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    #[must_use]
+    pub fn new(input: &'a str) -> Self {
+        Self::with_offset(input, 0)
+    }
+
+    /// Same as [`Tokenizer::new`], spans are shifted by `offset`.
+    ///
+    /// Useful to tokenize the content of a block, given the start of its span.
+    #[must_use]
+    pub fn with_offset(input: &'a str, offset: usize) -> Self {
+        Self {
+            input,
+            offset,
+            position: 0,
+            pending: None,
+            last_start_tag: None,
+            raw_text: None,
+        }
+    }
+
+    /// Return everything up to the end tag matching the last start tag as a single text token,
+    /// instead of tokenizing it.
+    pub fn enter_raw_text(&mut self) {
+        self.raw_text = self.last_start_tag.clone();
+    }
+
+    fn span(&self, range: Range<usize>) -> Range<usize> {
+        self.offset + range.start..self.offset + range.end
+    }
+
+    /// Returns the tag or comment at `index`, if any, along with its end.
+    fn token_at(&self, index: usize) -> Option<(Token<'a>, usize)> {
+        let input = &self.input[index..];
+
+        if let Some(comment) = input.strip_prefix("<!--") {
+            let (text, len) = match comment.find("-->") {
+                Some(end) => (&comment[..end], "<!--".len() + end + "-->".len()),
+                None => (comment, input.len()),
+            };
+
+            return Some((
+                Token::Comment {
+                    text,
+                    span: self.span(index..index + len),
+                },
+                index + len,
+            ));
+        }
+
+        if let Ok((remaining, name)) = parse_end_tag(input) {
+            let end = self.input.len() - remaining.len();
+
+            return Some((
+                Token::EndTag {
+                    name,
+                    span: self.span(index..end),
+                },
+                end,
+            ));
+        }
+
+        if let Ok((remaining, start_tag)) = parse_start_tag(input) {
+            let StartTag {
+                name,
+                attributes,
+                attribute_spans,
+                self_closing,
+            } = start_tag;
+            let end = self.input.len() - remaining.len();
+
+            return Some((
+                Token::StartTag {
+                    name,
+                    attributes,
+                    attribute_spans: attribute_spans
+                        .into_iter()
+                        .map(|span| self.span(index + span.start..index + span.end))
+                        .collect(),
+                    self_closing,
+                    span: self.span(index..end),
+                },
+                end,
+            ));
+        }
+
+        None
+    }
+
+    /// Returns the offset of the end tag closing the raw text content of `name`.
+    fn raw_text_end(&self, name: &BlockName) -> usize {
+        memchr::memmem::find_iter(&self.input.as_bytes()[self.position..], "</")
+            .map(|index| self.position + index)
+            .find(|&index| {
+                matches!(parse_end_tag(&self.input[index..]), Ok((_, other)) if other == *name)
+            })
+            .unwrap_or(self.input.len())
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(token) = self.pending.take() {
+            return Some(token);
+        }
+
+        if let Some(name) = self.raw_text.take() {
+            let end = self.raw_text_end(&name);
+
+            if end > self.position {
+                let start = std::mem::replace(&mut self.position, end);
+
+                return Some(Token::Text {
+                    text: &self.input[start..end],
+                    span: self.span(start..end),
+                });
+            }
+        }
+
+        if self.position >= self.input.len() {
+            return None;
+        }
+
+        let start = self.position;
+
+        let found = memchr::memchr_iter(b'<', &self.input.as_bytes()[start..])
+            .map(|index| start + index)
+            .find_map(|index| self.token_at(index).map(|(token, end)| (index, token, end)));
+
+        let token = match found {
+            Some((index, token, end)) => {
+                self.position = end;
+
+                if let Token::StartTag { name, .. } = &token {
+                    self.last_start_tag = Some(name.clone());
+                }
+
+                if index == start {
+                    return Some(token);
+                }
+
+                self.pending = Some(token);
+
+                index
+            }
+            None => {
+                self.position = self.input.len();
+                self.input.len()
+            }
+        };
+
+        Some(Token::Text {
+            text: &self.input[start..token],
+            span: self.span(start..token),
+        })
+    }
+}
+
+impl FusedIterator for Tokenizer<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Token, Tokenizer};
+
+    #[test]
+    fn test_tokenizer() {
+        let tokens = Tokenizer::with_offset("<p class=\"a\">a < b<!-- <p> --><br/></p>", 10)
+            .map(|token| (token.span(), token))
+            .collect::<Vec<_>>();
+
+        assert_eq!(tokens.len(), 5);
+        assert!(matches!(
+            &tokens[0],
+            (span, Token::StartTag { name, attributes, attribute_spans, self_closing: false, .. })
+                if *span == (10..23) && *name == "p" && attributes.len() == 1 && attribute_spans[0] == (13..22)
+        ));
+        assert!(
+            matches!(&tokens[1], (span, Token::Text { text: "a < b", .. }) if *span == (23..28))
+        );
+        assert!(matches!(
+            &tokens[2],
+            (_, Token::Comment { text: " <p> ", .. })
+        ));
+        assert!(matches!(
+            &tokens[3],
+            (
+                _,
+                Token::StartTag {
+                    self_closing: true,
+                    ..
+                }
+            )
+        ));
+        assert!(
+            matches!(&tokens[4], (span, Token::EndTag { name, .. }) if *span == (45..49) && *name == "p")
+        );
+    }
+
+    #[test]
+    fn test_tokenizer_raw_text() {
+        let mut tokenizer = Tokenizer::new("<script>a</b> <c></SCRIPT><style></style>");
+
+        assert!(matches!(tokenizer.next(), Some(Token::StartTag { .. })));
+        tokenizer.enter_raw_text();
+        assert!(matches!(
+            tokenizer.next(),
+            Some(Token::Text {
+                text: "a</b> <c>",
+                ..
+            })
+        ));
+        assert!(matches!(tokenizer.next(), Some(Token::EndTag { .. })));
+
+        // Empty raw text content.
+        assert!(matches!(tokenizer.next(), Some(Token::StartTag { .. })));
+        tokenizer.enter_raw_text();
+        assert!(matches!(tokenizer.next(), Some(Token::EndTag { .. })));
+        assert!(tokenizer.next().is_none());
+    }
+}
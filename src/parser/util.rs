@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
 use crate::{Attribute, AttributeName, AttributeValue, BlockName};
 
@@ -6,21 +6,40 @@ use nom::{
     branch::alt,
     bytes::complete::{tag, take_until, take_while1},
     character::complete::{char, multispace0, multispace1},
-    combinator::{fail, opt},
+    combinator::{consumed, fail, opt},
     multi::many0,
     sequence::{delimited, pair, preceded},
-    IResult, Parser,
+    IResult, Offset, Parser,
 };
 
+/// A start tag, as returned by [`parse_start_tag`].
+#[derive(Debug)]
+pub struct StartTag<'a> {
+    pub name: BlockName<'a>,
+    pub attributes: Vec<Attribute<'a>>,
+    /// Span of each attribute, relative to the start of the tag.
+    pub attribute_spans: Vec<Range<usize>>,
+    /// Whether the tag ends with `/>`.
+    pub self_closing: bool,
+}
+
 pub fn trim_start_newlines_end(input: &str) -> &str {
     input.trim_start_matches(['\n', '\r']).trim_end()
 }
 
+/// Same as [`trim_start_newlines_end`], but operates on a range of `input`.
+pub fn trim_start_newlines_end_range(input: &str, range: Range<usize>) -> Range<usize> {
+    let slice = &input[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start_matches(['\n', '\r']).len());
+
+    start..start + trim_start_newlines_end(slice).len()
+}
+
 /// # References
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#tag-open-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#end-tag-open-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#tag-name-state>
-pub fn parse_end_tag(input: &str) -> IResult<&str, BlockName> {
+pub fn parse_end_tag(input: &str) -> IResult<&str, BlockName<'_>> {
     delimited(tag("</"), parse_tag_name, preceded(multispace0, char('>'))).parse(input)
 }
 
@@ -28,21 +47,42 @@ pub fn parse_end_tag(input: &str) -> IResult<&str, BlockName> {
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#data-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#tag-name-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-name-state>
-pub fn parse_start_tag(input: &str) -> IResult<&str, (BlockName, Vec<Attribute>)> {
-    delimited(
-        char('<'),
-        pair(
-            parse_tag_name,
-            many0(preceded(multispace1, parse_start_tag_attribute)),
+/// - <https://html.spec.whatwg.org/multipage/parsing.html#self-closing-start-tag-state>
+pub fn parse_start_tag(input: &str) -> IResult<&str, StartTag<'_>> {
+    let (remaining, ((name, attributes), self_closing)) = pair(
+        preceded(
+            char('<'),
+            pair(
+                parse_tag_name,
+                many0(preceded(multispace1, consumed(parse_start_tag_attribute))),
+            ),
         ),
-        preceded(multispace0, char('>')),
+        delimited(multispace0, opt(char('/')), char('>')),
     )
-    .parse(input)
+    .parse(input)?;
+
+    let (attribute_spans, attributes) = attributes
+        .into_iter()
+        .map(|(consumed, attribute)| {
+            let start = input.offset(consumed);
+            (start..start + consumed.len(), attribute)
+        })
+        .unzip();
+
+    Ok((
+        remaining,
+        StartTag {
+            name,
+            attributes,
+            attribute_spans,
+            self_closing: self_closing.is_some(),
+        },
+    ))
 }
 
 /// # References
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#after-attribute-name-state>
-fn parse_start_tag_attribute(input: &str) -> IResult<&str, Attribute> {
+fn parse_start_tag_attribute(input: &str) -> IResult<&str, Attribute<'_>> {
     pair(
         parse_start_tag_attribute_name,
         opt(preceded(
@@ -58,7 +98,7 @@ fn parse_start_tag_attribute(input: &str) -> IResult<&str, Attribute> {
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(double-quoted)-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(single-quoted)-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#attribute-value-(unquoted)-state>
-fn parse_start_tag_attribute_value(input: &str) -> IResult<&str, AttributeValue> {
+fn parse_start_tag_attribute_value(input: &str) -> IResult<&str, AttributeValue<'_>> {
     alt((
         delimited(char('\u{0022}'), take_until("\u{0022}"), char('\u{0022}')),
         delimited(char('\u{0027}'), take_until("\u{0027}"), char('\u{0027}')),
@@ -69,14 +109,14 @@ fn parse_start_tag_attribute_value(input: &str) -> IResult<&str, AttributeValue>
             )
         }),
     ))
-    .map(|str| unsafe { AttributeValue::from_cow_unchecked(Cow::Borrowed(str)) })
+    .map(|str| unsafe { AttributeValue::from_raw_cow_unchecked(Cow::Borrowed(str)) })
     .parse(input)
 }
 
 /// # References
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#before-attribute-name-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#attribute-name-state>
-fn parse_start_tag_attribute_name(input: &str) -> IResult<&str, AttributeName> {
+fn parse_start_tag_attribute_name(input: &str) -> IResult<&str, AttributeName<'_>> {
     take_while1(|ch: char| {
         !matches!(
             ch,
@@ -96,7 +136,7 @@ fn parse_start_tag_attribute_name(input: &str) -> IResult<&str, AttributeName> {
 /// # References
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#tag-open-state>
 /// - <https://html.spec.whatwg.org/multipage/parsing.html#tag-name-state>
-fn parse_tag_name(input: &str) -> IResult<&str, BlockName> {
+fn parse_tag_name(input: &str) -> IResult<&str, BlockName<'_>> {
     if !input.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
         return fail(input);
     }
@@ -0,0 +1,653 @@
+//! Conversion of pug templates to HTML.
+//!
+//! Supports the subset of pug commonly found in Vue templates: tags with class & id shorthands,
+//! attributes, inline, piped & block text, block expansion, self-closing tags, comments and
+//! literal HTML. Code, conditionals, iteration, mixins, includes & inheritance are not supported.
+//!
+//! Each line of the generated HTML is mapped back to the pug line it comes from.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::{pug, Section};
+//!
+//! let sfc = vue_sfc::parse(concat!(
+//!     "<template lang=\"pug\">\n",
+//!     "ul#list\n",
+//!     "  li.item(v-for=\"item in items\" :key=\"item.id\") {{ item.name }}\n",
+//!     "</template>",
+//! ))
+//! .unwrap();
+//!
+//! let Section::Block(block) = &sfc[0] else { unreachable!() };
+//! let converted = pug::convert(block).unwrap();
+//!
+//! assert_eq!(
+//!     converted.block.to_string(),
+//!     concat!(
+//!         "<template>\n",
+//!         "<ul id=\"list\">\n",
+//!         "  <li class=\"item\" v-for=\"item in items\" :key=\"item.id\">{{ item.name }}</li>\n",
+//!         "</ul>\n",
+//!         "</template>",
+//!     )
+//! );
+//! assert_eq!(converted.html.pug_line(1), Some(1));
+//! ```
+
+use std::{borrow::Cow, fmt::Write};
+
+use crate::{AttributeValue, Block};
+
+pub use self::error::PugError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    /// Returned when [`to_html`][super::to_html] fails.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[non_exhaustive]
+    pub enum PugError {
+        /// A valid pug construct which is not supported, i.e: conditionals.
+        Unsupported(#[doc(hidden)] String, #[doc(hidden)] usize),
+        /// Invalid pug, i.e: an unterminated attribute list.
+        InvalidSyntax(#[doc(hidden)] String, #[doc(hidden)] usize),
+    }
+
+    impl PugError {
+        /// Zero-based line, in the pug input, at which the error occurred. [`Display`] prints it
+        /// one-based.
+        #[must_use]
+        pub fn line(&self) -> usize {
+            match self {
+                Self::Unsupported(_, line) | Self::InvalidSyntax(_, line) => *line,
+            }
+        }
+    }
+
+    impl Display for PugError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Unsupported(construct, line) => {
+                    write!(
+                        f,
+                        "unsupported pug construct on line {}: {construct}",
+                        line + 1
+                    )
+                }
+                Self::InvalidSyntax(message, line) => {
+                    write!(f, "invalid pug syntax on line {}: {message}", line + 1)
+                }
+            }
+        }
+    }
+
+    impl Error for PugError {}
+}
+
+/// Keywords introducing constructs which are not supported.
+const KEYWORDS: [&str; 16] = [
+    "if", "else", "unless", "each", "for", "while", "case", "when", "default", "mixin", "include",
+    "extends", "block", "append", "prepend", "yield",
+];
+
+/// Elements without end tag.
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#void-elements>.
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Indentation of generated HTML, per level.
+const INDENT: &str = "  ";
+
+/// HTML generated by [`to_html`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Html {
+    pub html: String,
+    /// The (zero-based) pug line of each (zero-based) HTML line.
+    pub lines: Vec<usize>,
+}
+
+impl Html {
+    /// Returns the (zero-based) pug line the given (zero-based) HTML line comes from.
+    #[must_use]
+    pub fn pug_line(&self, html_line: usize) -> Option<usize> {
+        self.lines.get(html_line).copied()
+    }
+
+    fn push_line(&mut self, depth: usize, line: &str, pug_line: usize) {
+        if !self.lines.is_empty() {
+            self.html.push('\n');
+        }
+
+        self.html.push_str(&INDENT.repeat(depth));
+        self.html.push_str(line);
+        self.lines.push(pug_line);
+    }
+}
+
+/// The result of [`convert`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Converted<'a> {
+    /// The block, with HTML content and without `lang` attribute.
+    pub block: Block<'a>,
+    /// Line mappings, `html` is the content of `block`.
+    pub html: Html,
+}
+
+/// Convert the pug content of `block` to HTML, removing its `lang` attribute(s).
+///
+/// # Errors
+/// Will return an error if the content is not valid or not supported pug.
+pub fn convert<'a>(block: &Block<'a>) -> Result<Converted<'a>, PugError> {
+    let html = to_html(&block.content)?;
+
+    Ok(Converted {
+        block: Block {
+            name: block.name.clone(),
+            attributes: block
+                .attributes
+                .iter()
+                .filter(|(name, _)| *name != "lang")
+                .cloned()
+                .collect(),
+            content: Cow::Owned(html.html.clone()),
+        },
+        html,
+    })
+}
+
+/// An element whose end tag has not been written yet.
+struct Open<'p> {
+    indent: usize,
+    name: &'p str,
+    depth: usize,
+    /// HTML line of the start tag.
+    line: usize,
+    pug_line: usize,
+}
+
+/// Convert pug to HTML.
+///
+/// # Errors
+/// Will return an error if the input is not valid or not supported pug.
+pub fn to_html(pug: &str) -> Result<Html, PugError> {
+    let mut html = Html::default();
+    let mut stack: Vec<Open> = Vec::new();
+    let mut lines = pug.lines().enumerate().peekable();
+
+    while let Some((pug_line, line)) = lines.next() {
+        let text = line.trim_start();
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let indent = line.len() - text.len();
+
+        close(&mut html, &mut stack, indent);
+
+        let depth = stack.last().map_or(0, |open| open.depth + 1);
+
+        // Lines indented more than `indent`, for comments & block text.
+        let mut nested = || {
+            let mut nested = Vec::new();
+
+            while let Some((pug_line, line)) = lines.next_if(|(_, line)| {
+                let text = line.trim_start();
+                text.is_empty() || line.len() - text.len() > indent
+            }) {
+                nested.push((pug_line, line));
+            }
+
+            // Do not keep trailing blank lines.
+            while nested
+                .last()
+                .is_some_and(|(_, line)| line.trim().is_empty())
+            {
+                nested.pop();
+            }
+
+            nested
+        };
+
+        if text.starts_with("//-") {
+            nested();
+        } else if let Some(comment) = text.strip_prefix("//") {
+            let nested = nested();
+
+            if nested.is_empty() {
+                html.push_line(depth, &format!("<!--{comment} -->"), pug_line);
+            } else {
+                html.push_line(depth, &format!("<!--{comment}"), pug_line);
+                push_text_block(&mut html, depth + 1, &nested);
+                html.push_line(depth, "-->", pug_line);
+            }
+        } else if let Some(text) = text.strip_prefix('|') {
+            html.push_line(depth, text.strip_prefix(' ').unwrap_or(text), pug_line);
+        } else if text.starts_with('<') {
+            html.push_line(depth, text, pug_line);
+        } else {
+            let mut rest = text;
+            let mut depth = depth;
+
+            loop {
+                let element = parse_element(rest, pug_line)?;
+                let mut start_tag = format!("<{}", element.name);
+
+                for (name, value) in &element.attributes {
+                    match value {
+                        Some(value) => {
+                            let value = AttributeValue::from_cow(Cow::Borrowed(value.as_str()))
+                                .map_err(|err| {
+                                    PugError::InvalidSyntax(err.to_string(), pug_line)
+                                })?;
+
+                            let _ = write!(start_tag, " {name}=");
+                            let _ = value.write_quoted(&mut start_tag, '"');
+                        }
+                        None => {
+                            let _ = write!(start_tag, " {name}");
+                        }
+                    }
+                }
+
+                match element.rest {
+                    Rest::SelfClosing => {
+                        start_tag.push_str(" />");
+                        html.push_line(depth, &start_tag, pug_line);
+                    }
+                    Rest::Text(text) => {
+                        start_tag.push('>');
+                        start_tag.push_str(text);
+                        html.push_line(depth, &start_tag, pug_line);
+                        push_open(&mut stack, &html, indent, element.name, depth, pug_line);
+                    }
+                    Rest::Block => {
+                        start_tag.push('>');
+                        html.push_line(depth, &start_tag, pug_line);
+                        push_open(&mut stack, &html, indent, element.name, depth, pug_line);
+                        push_text_block(&mut html, depth + 1, &nested());
+                    }
+                    Rest::Expansion(expansion) => {
+                        start_tag.push('>');
+                        html.push_line(depth, &start_tag, pug_line);
+                        push_open(&mut stack, &html, indent, element.name, depth, pug_line);
+
+                        rest = expansion;
+                        depth += 1;
+                        continue;
+                    }
+                }
+
+                break;
+            }
+        }
+    }
+
+    close(&mut html, &mut stack, 0);
+
+    Ok(html)
+}
+
+fn push_open<'p>(
+    stack: &mut Vec<Open<'p>>,
+    html: &Html,
+    indent: usize,
+    name: &'p str,
+    depth: usize,
+    pug_line: usize,
+) {
+    if VOID_ELEMENTS
+        .iter()
+        .any(|void| void.eq_ignore_ascii_case(name))
+    {
+        return;
+    }
+
+    stack.push(Open {
+        indent,
+        name,
+        depth,
+        line: html.lines.len() - 1,
+        pug_line,
+    });
+}
+
+/// Write the end tag of elements indented at least by `indent`.
+fn close(html: &mut Html, stack: &mut Vec<Open>, indent: usize) {
+    while stack.last().is_some_and(|open| open.indent >= indent) {
+        let Some(open) = stack.pop() else { break };
+
+        if open.line + 1 == html.lines.len() {
+            // Without content, or with inline text only.
+            let _ = write!(html.html, "</{}>", open.name);
+        } else {
+            html.push_line(open.depth, &format!("</{}>", open.name), open.pug_line);
+        }
+    }
+}
+
+/// Write text lines, keeping their indentation relative to the least indented one.
+fn push_text_block(html: &mut Html, depth: usize, lines: &[(usize, &str)]) {
+    let common = lines
+        .iter()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(_, line)| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    for &(pug_line, line) in lines {
+        if line.trim().is_empty() {
+            html.push_line(0, "", pug_line);
+        } else {
+            html.push_line(depth, &line[common..], pug_line);
+        }
+    }
+}
+
+/// An element, as parsed by [`parse_element`].
+struct Element<'p> {
+    name: &'p str,
+    attributes: Vec<(String, Option<String>)>,
+    rest: Rest<'p>,
+}
+
+/// What follows the tag, class, id & attributes of an element.
+enum Rest<'p> {
+    /// `img/`.
+    SelfClosing,
+    /// `p text`, the text may be empty.
+    Text(&'p str),
+    /// `p.`, followed by indented text.
+    Block,
+    /// `li: a`.
+    Expansion(&'p str),
+}
+
+fn parse_element(input: &str, line: usize) -> Result<Element<'_>, PugError> {
+    let word = input
+        .split(|ch: char| ch.is_whitespace() || ch == '(')
+        .next()
+        .unwrap_or_default();
+
+    if KEYWORDS.contains(&word) {
+        return Err(PugError::Unsupported(format!("`{word}`"), line));
+    }
+
+    match input.chars().next() {
+        Some('-' | '=' | '+') => {
+            return Err(PugError::Unsupported(
+                String::from("code, mixin call or buffered code"),
+                line,
+            ));
+        }
+        Some(ch) if !ch.is_ascii_alphabetic() && ch != '.' && ch != '#' => {
+            return Err(PugError::InvalidSyntax(
+                format!("unexpected character `{ch}`"),
+                line,
+            ));
+        }
+        _ => {}
+    }
+
+    // Tag names may contain `-` & `:`, but not end with them, i.e: `li: a`.
+    let name_len = input
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | ':')))
+        .map_or(input, |len| &input[..len])
+        .trim_end_matches(['-', ':'])
+        .len();
+
+    let name = if name_len == 0 {
+        "div"
+    } else {
+        &input[..name_len]
+    };
+
+    let mut rest = &input[name_len..];
+    let mut classes = Vec::new();
+    let mut id = None;
+    let mut attributes = Vec::new();
+
+    loop {
+        if let Some(after) = rest.strip_prefix(['.', '#']) {
+            let len = after
+                .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '-' | '_')))
+                .unwrap_or(after.len());
+
+            if len == 0 {
+                break;
+            }
+
+            if rest.starts_with('.') {
+                classes.push(after[..len].to_owned());
+            } else {
+                id = Some(after[..len].to_owned());
+            }
+
+            rest = &after[len..];
+        } else if rest.starts_with('(') {
+            rest = parse_attributes(rest, &mut attributes, line)?;
+        } else if rest.starts_with("&attributes") {
+            return Err(PugError::Unsupported(String::from("`&attributes`"), line));
+        } else {
+            break;
+        }
+    }
+
+    // Merge shorthand classes with the `class` attribute, and the shorthand id first.
+    if let Some(index) = attributes.iter().position(|(name, _)| name == "class") {
+        if let (_, Some(value)) = attributes.remove(index) {
+            classes.push(value);
+        }
+    }
+
+    let mut merged = Vec::with_capacity(attributes.len() + 2);
+
+    if let Some(id) = id {
+        merged.push((String::from("id"), Some(id)));
+    }
+
+    if !classes.is_empty() {
+        merged.push((String::from("class"), Some(classes.join(" "))));
+    }
+
+    merged.extend(attributes);
+
+    let rest = if rest == "/" {
+        Rest::SelfClosing
+    } else if rest == "." {
+        Rest::Block
+    } else if let Some(expansion) = rest.strip_prefix(':') {
+        Rest::Expansion(expansion.trim_start())
+    } else if rest.starts_with(['=', '!']) {
+        return Err(PugError::Unsupported(String::from("buffered code"), line));
+    } else if rest.is_empty() {
+        Rest::Text("")
+    } else if let Some(text) = rest.strip_prefix(' ') {
+        Rest::Text(text)
+    } else {
+        return Err(PugError::InvalidSyntax(
+            format!("unexpected `{rest}`"),
+            line,
+        ));
+    };
+
+    Ok(Element {
+        name,
+        attributes: merged,
+        rest,
+    })
+}
+
+/// Parse `(name="value", other)`, returning the remaining input.
+fn parse_attributes<'p>(
+    input: &'p str,
+    attributes: &mut Vec<(String, Option<String>)>,
+    line: usize,
+) -> Result<&'p str, PugError> {
+    let mut rest = &input[1..];
+
+    loop {
+        rest = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+
+        if let Some(rest) = rest.strip_prefix(')') {
+            return Ok(rest);
+        }
+
+        if rest.is_empty() {
+            return Err(PugError::InvalidSyntax(
+                String::from("unterminated attributes"),
+                line,
+            ));
+        }
+
+        let name_len = rest
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '=' | ',' | ')' | '!'))
+            .unwrap_or(rest.len());
+        let name = &rest[..name_len];
+        rest = rest[name_len..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix("!=").or_else(|| rest.strip_prefix('='))
+        {
+            let (value, after) = parse_attribute_value(after.trim_start(), line)?;
+            rest = after;
+            value
+        } else {
+            None
+        };
+
+        attributes.push((name.to_owned(), value));
+    }
+}
+
+/// Parse an attribute value, returning `None` for `true` and the remaining input.
+fn parse_attribute_value(input: &str, line: usize) -> Result<(Option<String>, &str), PugError> {
+    if let Some(quote) = input
+        .chars()
+        .next()
+        .filter(|ch| matches!(ch, '"' | '\'' | '`'))
+    {
+        let mut value = String::new();
+        let mut chars = input.char_indices().skip(1);
+
+        while let Some((index, ch)) = chars.next() {
+            match ch {
+                '\\' => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(escaped);
+                    }
+                }
+                ch if ch == quote => return Ok((Some(value), &input[index + 1..])),
+                ch => value.push(ch),
+            }
+        }
+
+        return Err(PugError::InvalidSyntax(
+            String::from("unterminated attribute value"),
+            line,
+        ));
+    }
+
+    let len = input
+        .find(|ch: char| ch.is_whitespace() || matches!(ch, ',' | ')'))
+        .unwrap_or(input.len());
+
+    match &input[..len] {
+        "true" => Ok((None, &input[len..])),
+        number if !number.is_empty() && number.parse::<f64>().is_ok() => {
+            Ok((Some(number.to_owned()), &input[len..]))
+        }
+        value => Err(PugError::Unsupported(
+            format!("unquoted attribute value `{value}`"),
+            line,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_html, PugError};
+
+    #[test]
+    fn test_to_html() {
+        let html = to_html(concat!(
+            "//- hidden\n",
+            "  still hidden\n",
+            "// visible\n",
+            "div#app.a.b(class=\"c\" :class=\"{ d: e }\", disabled)\n",
+            "  p Hello, {{ name }}!\n",
+            "\n",
+            "  p\n",
+            "    | text\n",
+            "    a(href='#') link\n",
+            "  img(src=\"a.png\")\n",
+            "  my-component/\n",
+            "  ul: li: a(href=\"#\") item\n",
+            "  pre.\n",
+            "    line\n",
+            "      indented\n",
+            "  <br>\n",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            html.html,
+            concat!(
+                "<!-- visible -->\n",
+                "<div id=\"app\" class=\"a b c\" :class=\"{ d: e }\" disabled>\n",
+                "  <p>Hello, {{ name }}!</p>\n",
+                "  <p>\n",
+                "    text\n",
+                "    <a href=\"#\">link</a>\n",
+                "  </p>\n",
+                "  <img src=\"a.png\">\n",
+                "  <my-component />\n",
+                "  <ul>\n",
+                "    <li>\n",
+                "      <a href=\"#\">item</a>\n",
+                "    </li>\n",
+                "  </ul>\n",
+                "  <pre>\n",
+                "    line\n",
+                "      indented\n",
+                "  </pre>\n",
+                "  <br>\n",
+                "</div>",
+            )
+        );
+
+        assert_eq!(
+            html.lines,
+            vec![2, 3, 4, 6, 7, 8, 6, 9, 10, 11, 11, 11, 11, 11, 12, 13, 14, 12, 15, 3]
+        );
+    }
+
+    #[test]
+    fn test_to_html_quotes() {
+        let html = to_html("p(title=\"it's a \\\"x\\\"\") a").unwrap();
+
+        assert_eq!(html.html, "<p title=\"it's a &quot;x&quot;\">a</p>");
+    }
+
+    #[test]
+    fn test_to_html_errors() {
+        assert!(matches!(
+            to_html("div\n  if a\n    p"),
+            Err(PugError::Unsupported(_, 1))
+        ));
+        assert!(matches!(
+            to_html("p= message"),
+            Err(PugError::Unsupported(_, 0))
+        ));
+        assert!(matches!(
+            to_html("a(href=\"#)"),
+            Err(PugError::InvalidSyntax(_, 0))
+        ));
+
+        let err = to_html("div\n  if a\n    p").unwrap_err();
+        assert_eq!(err.line(), 1);
+        assert_eq!(err.to_string(), "unsupported pug construct on line 2: `if`");
+    }
+}
@@ -0,0 +1,582 @@
+//! Walk a project directory and index the Vue SFCs it contains.
+//!
+//! Ignore files (`.gitignore`, `.ignore`, ...) are respected, files are selected with
+//! [`ScanOptions::include`] & [`ScanOptions::exclude`] globs and parsed with [`parse_batch`].
+//!
+//! [`walk`] selects files the same way, without parsing them.
+//!
+//! With the `serde` feature enabled, [`ComponentIndex`] can be serialized, i.e: so that other tools
+//! can query it without scanning the project again.
+//!
+//! # Example
+//! ```rust,no_run
+//! use vue_sfc::scan::{scan, ScanOptions};
+//!
+//! let index = scan("./src", &ScanOptions::default()).unwrap();
+//!
+//! for component in index.named("TheHeader") {
+//!     println!("{}", component.path.display());
+//! }
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+
+use crate::{
+    batch::{parse_batch, BatchInput},
+    parser::ParseOptions,
+    Sfc,
+};
+
+pub use self::error::ScanError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+
+    /// Returned when [`scan`][super::scan] fails.
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum ScanError {
+        /// An include or exclude glob is invalid.
+        Glob(globset::Error),
+        /// The project directory could not be read.
+        Walk(ignore::Error),
+    }
+
+    impl Display for ScanError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Glob(err) => write!(f, "invalid glob: {err}"),
+                Self::Walk(err) => write!(f, "unable to walk directory: {err}"),
+            }
+        }
+    }
+
+    impl Error for ScanError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::Glob(err) => Some(err),
+                Self::Walk(err) => Some(err),
+            }
+        }
+    }
+
+    impl From<globset::Error> for ScanError {
+        fn from(err: globset::Error) -> Self {
+            Self::Glob(err)
+        }
+    }
+
+    impl From<ignore::Error> for ScanError {
+        fn from(err: ignore::Error) -> Self {
+            Self::Walk(err)
+        }
+    }
+}
+
+/// Options of [`scan`].
+///
+/// Globs are matched against paths relative to the scanned directory, `*` does not match `/`.
+#[derive(Clone, Debug)]
+pub struct ScanOptions {
+    /// Files matching any of these globs are indexed.
+    pub include: Vec<String>,
+    /// Files & directories matching any of these globs are skipped, excluded directories are not
+    /// descended into.
+    pub exclude: Vec<String>,
+    /// Respect ignore files: `.gitignore`, `.ignore`, global git excludes, ...
+    pub ignore_files: bool,
+    /// Walk hidden files & directories.
+    pub hidden: bool,
+    pub parse: ParseOptions,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            include: vec![String::from("**/*.vue")],
+            exclude: vec![String::from("**/node_modules")],
+            ignore_files: true,
+            hidden: false,
+            parse: ParseOptions::default(),
+        }
+    }
+}
+
+/// The result of [`scan`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComponentIndex {
+    /// The scanned directory.
+    pub root: PathBuf,
+    /// Indexed components, sorted by path.
+    pub components: Vec<Component>,
+    /// Errors encountered while walking the project, i.e: an unreadable directory or an invalid
+    /// ignore file. The rest of the project is still indexed.
+    pub errors: Vec<WalkError>,
+}
+
+impl ComponentIndex {
+    /// Components named `name`, several components may share the same name.
+    pub fn named<'s>(&'s self, name: &'s str) -> impl Iterator<Item = &'s Component> {
+        self.components
+            .iter()
+            .filter(move |component| component.name == name)
+    }
+
+    /// The component at `path`, relative to [`ComponentIndex::root`].
+    #[must_use]
+    pub fn get(&self, path: &Path) -> Option<&Component> {
+        self.components
+            .iter()
+            .find(|component| component.path == path)
+    }
+
+    /// Components which could not be read or parsed.
+    pub fn failed(&self) -> impl Iterator<Item = &Component> {
+        self.components
+            .iter()
+            .filter(|component| matches!(component.status, ComponentStatus::Failed { .. }))
+    }
+}
+
+/// A Vue SFC, see [`ComponentIndex`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Component {
+    /// The `name` option of the component when found in a `<script>` block, i.e:
+    /// `export default { name: 'TheHeader' }` or `defineOptions({ name: 'TheHeader' })`, the file
+    /// stem otherwise.
+    pub name: String,
+    /// Path of the file, relative to [`ComponentIndex::root`].
+    pub path: PathBuf,
+    /// Blocks of the component, in order, empty if it could not be parsed.
+    pub blocks: Vec<IndexedBlock>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub status: ComponentStatus,
+}
+
+/// A block of a [`Component`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexedBlock {
+    pub name: String,
+    /// Value of the `lang` attribute.
+    pub lang: Option<String>,
+}
+
+/// An error encountered while walking a project, see [`ComponentIndex::errors`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WalkError {
+    /// Path of the offending file or directory, relative to [`ComponentIndex::root`], when known.
+    pub path: Option<PathBuf>,
+    pub error: String,
+}
+
+/// Whether a [`Component`] could be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "status", rename_all = "lowercase"))]
+pub enum ComponentStatus {
+    Parsed,
+    /// The file could not be read or parsed.
+    Failed {
+        error: String,
+    },
+}
+
+/// Walk `root` and index the Vue SFCs it contains.
+///
+/// Files failing to be read or parsed are indexed with a [`ComponentStatus::Failed`] status,
+/// other errors encountered while walking are listed in [`ComponentIndex::errors`].
+///
+/// # Errors
+/// Will return an error if a glob is invalid or if `root` cannot be read.
+pub fn scan(root: impl AsRef<Path>, options: &ScanOptions) -> Result<ComponentIndex, ScanError> {
+    scan_with(root.as_ref(), options, |_, _| {})
+}
+
+/// Same as [`scan`], calling `f` with each parsed component.
+pub(crate) fn scan_with(
+    root: &Path,
+    options: &ScanOptions,
+    mut f: impl FnMut(&Component, &Sfc),
+) -> Result<ComponentIndex, ScanError> {
+    let (paths, errors) = walk(root, options)?;
+
+    let batch = parse_batch(
+        paths
+            .iter()
+            .map(|path| BatchInput::Path(root.join(path)))
+            .collect(),
+        &options.parse,
+    );
+
+    let components = paths
+        .into_iter()
+        .zip(batch.files)
+        .map(|(path, file)| {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            match file.result {
+                Ok(sfc) => {
+                    let sfc = sfc.sfc();
+
+                    let component = Component {
+                        name: name_option(&sfc).unwrap_or(stem),
+                        path,
+                        blocks: sfc
+                            .blocks()
+                            .map(|block| IndexedBlock {
+                                name: block.name.to_string(),
+                                lang: block
+                                    .attribute_value("lang")
+                                    .map(|lang| lang.as_str().to_owned()),
+                            })
+                            .collect(),
+                        status: ComponentStatus::Parsed,
+                    };
+
+                    f(&component, &sfc);
+
+                    component
+                }
+                Err(err) => Component {
+                    name: stem,
+                    path,
+                    blocks: Vec::new(),
+                    status: ComponentStatus::Failed {
+                        error: err.to_string(),
+                    },
+                },
+            }
+        })
+        .collect();
+
+    Ok(ComponentIndex {
+        root: root.to_owned(),
+        components,
+        errors,
+    })
+}
+
+/// The path an error of the walk is about, if any.
+fn error_path(err: &ignore::Error) -> Option<&Path> {
+    match err {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        ignore::Error::Loop { child, .. } => Some(child),
+        _ => None,
+    }
+}
+
+/// Walk `root` as [`scan`] does, without parsing: returns the paths of the selected files,
+/// relative to `root` and sorted, along with the errors encountered while walking.
+///
+/// # Errors
+/// Will return an error if a glob is invalid or if `root` cannot be read.
+pub fn walk(
+    root: impl AsRef<Path>,
+    options: &ScanOptions,
+) -> Result<(Vec<PathBuf>, Vec<WalkError>), ScanError> {
+    let root = root.as_ref();
+    let include = glob_set(&options.include)?;
+    let exclude = glob_set(&options.exclude)?;
+    let prefix = root.to_owned();
+
+    fs::metadata(root).map_err(|err| {
+        ScanError::Walk(ignore::Error::WithPath {
+            path: root.to_owned(),
+            err: Box::new(ignore::Error::Io(err)),
+        })
+    })?;
+
+    let walker = WalkBuilder::new(root)
+        .standard_filters(options.ignore_files)
+        .hidden(!options.hidden)
+        .require_git(false)
+        .sort_by_file_name(Ord::cmp)
+        .filter_entry(move |entry| {
+            let path = entry.path().strip_prefix(&prefix).unwrap_or(entry.path());
+
+            path.as_os_str().is_empty() || !exclude.is_match(path)
+        })
+        .build();
+
+    let mut paths = Vec::new();
+    let mut errors = Vec::new();
+
+    let walk_error = |err: &ignore::Error| WalkError {
+        path: error_path(err).map(|path| path.strip_prefix(root).unwrap_or(path).to_owned()),
+        error: err.to_string(),
+    };
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                errors.push(walk_error(&err));
+                continue;
+            }
+        };
+
+        // I.e: an invalid ignore file in this directory.
+        if let Some(err) = entry.error() {
+            errors.push(walk_error(err));
+        }
+
+        let path = entry.path().strip_prefix(root).unwrap_or(entry.path());
+
+        if entry.file_type().is_some_and(|kind| kind.is_file()) && include.is_match(path) {
+            paths.push(path.to_owned());
+        }
+    }
+
+    Ok((paths, errors))
+}
+
+fn glob_set(globs: &[String]) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for glob in globs {
+        builder.add(GlobBuilder::new(glob).literal_separator(true).build()?);
+    }
+
+    builder.build()
+}
+
+/// Find the `name` option of the component in its `<script>` blocks.
+///
+/// This is a lexical search for a `name: '...'` property of the object following
+/// `export default`, `defineComponent(` or `defineOptions(`, not a JavaScript parser.
+fn name_option(sfc: &Sfc) -> Option<String> {
+    sfc.blocks_named("script").find_map(|block| {
+        let content = block.content.as_ref();
+
+        ["export default", "defineComponent(", "defineOptions("]
+            .iter()
+            .filter_map(|marker| content.find(marker).map(|index| index + marker.len()))
+            .min()
+            .and_then(|start| name_property(&content[start..]))
+    })
+}
+
+/// Returns the value of the `name: '...'` property (any quote) of the first object of `input`.
+///
+/// Only properties of the object itself are considered: nested objects, calls, arrays, strings &
+/// comments are skipped, i.e: `data: () => ({ name: 'foo' })`.
+fn name_property(input: &str) -> Option<String> {
+    let is_ident = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$';
+
+    let mut index = input.find('{')?;
+    let mut depth = 0_usize;
+
+    while let Some(ch) = input[index..].chars().next() {
+        let rest = &input[index..];
+
+        match ch {
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return None;
+                }
+            }
+            '\'' | '"' | '`' => {
+                index += string_len(rest, ch)?;
+                continue;
+            }
+            '/' if rest.starts_with("//") => {
+                index += rest.find('\n')?;
+                continue;
+            }
+            '/' if rest.starts_with("/*") => {
+                index += rest.find("*/")? + 2;
+                continue;
+            }
+            'n' if depth == 1
+                && rest.starts_with("name")
+                && !input[..index].ends_with(is_ident) =>
+            {
+                let value = rest["name".len()..].trim_start();
+
+                if let Some(value) = value.strip_prefix(':') {
+                    let value = value.trim_start();
+                    let quote = value
+                        .chars()
+                        .next()
+                        .filter(|ch| matches!(ch, '\'' | '"' | '`'))?;
+                    let value = &value[1..];
+
+                    return value.find(quote).map(|end| value[..end].to_owned());
+                }
+            }
+            _ => {}
+        }
+
+        index += ch.len_utf8();
+    }
+
+    None
+}
+
+/// Length of the string literal starting with `quote` at the start of `input`, quotes included.
+fn string_len(input: &str, quote: char) -> Option<usize> {
+    let mut chars = input.char_indices().skip(1);
+
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            ch if ch == quote => return Some(index + ch.len_utf8()),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{name_property, scan, walk, ComponentStatus, IndexedBlock, ScanError, ScanOptions};
+
+    #[test]
+    fn test_name_property() {
+        assert_eq!(
+            name_property(" {\n  name: 'A',\n  components: {} }").as_deref(),
+            Some("A")
+        );
+        assert_eq!(
+            name_property("({ displayName: \"B\", name: `C` })").as_deref(),
+            Some("C")
+        );
+        assert_eq!(name_property("{ name }"), None);
+        assert_eq!(
+            name_property(concat!(
+                " {\n",
+                "  data: () => ({ name: 'foo' }),\n",
+                "  props: { name: { type: String, default: 'bar' } },\n",
+                "  // name: 'comment',\n",
+                "  title: '{ name: \\'string\\' }',\n",
+                "  name: 'Real',\n",
+                "}",
+            ))
+            .as_deref(),
+            Some("Real")
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        let root = std::env::temp_dir().join(format!("vue-sfc-scan-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+
+        write(".gitignore", "dist/\n");
+        write("src/.ignore", "[z-a]\n");
+        write(
+            "src/A.vue",
+            "<template></template>\n<script lang=\"ts\">export default { name: 'Named' }</script>",
+        );
+        write("src/b/B.vue", "</template>");
+        write("src/C.txt", "");
+        write("dist/D.vue", "");
+        write("node_modules/e/E.vue", "");
+        write("legacy/F.vue", "");
+
+        let options = ScanOptions {
+            exclude: vec![String::from("**/node_modules"), String::from("legacy")],
+            ..ScanOptions::default()
+        };
+
+        let index = scan(&root, &options).unwrap();
+        let walked = walk(&root, &options).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let paths = index
+            .components
+            .iter()
+            .map(|component| component.path.as_path())
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, [Path::new("src/A.vue"), Path::new("src/b/B.vue")]);
+        assert_eq!(walked.0, paths);
+        assert_eq!(walked.1, index.errors);
+
+        let a = index.get(Path::new("src/A.vue")).unwrap();
+        assert_eq!(a.name, "Named");
+        assert_eq!(a.status, ComponentStatus::Parsed);
+        assert_eq!(
+            a.blocks[1],
+            IndexedBlock {
+                name: String::from("script"),
+                lang: Some(String::from("ts")),
+            }
+        );
+
+        assert_eq!(index.named("B").count(), 1);
+        assert_eq!(index.failed().count(), 1);
+
+        assert_eq!(index.errors.len(), 1);
+        assert_eq!(
+            index.errors[0].path.as_deref(),
+            Some(Path::new("src/.ignore"))
+        );
+
+        assert!(matches!(
+            scan(root.join("missing"), &options),
+            Err(ScanError::Walk(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize() {
+        use super::Component;
+
+        let component = Component {
+            name: String::from("A"),
+            path: Path::new("src/A.vue").to_owned(),
+            blocks: Vec::new(),
+            status: ComponentStatus::Failed {
+                error: String::from("unexpected end tag: `template`"),
+            },
+        };
+
+        let value = serde_json::to_value(&component).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "name": "A",
+                "path": "src/A.vue",
+                "blocks": [],
+                "status": "failed",
+                "error": "unexpected end tag: `template`",
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Component>(value).unwrap(),
+            component
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! Split a Vue SFC into one file per block, and join such files back into a Vue SFC.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::split::{join, split};
+//!
+//! let sections = vue_sfc::parse("<script lang=\"ts\">\nlet a = 1;\n</script>").unwrap();
+//! let split = split("Component", &sections);
+//!
+//! assert_eq!(split.files[0].name, "Component.script.ts");
+//! assert_eq!(split.files[0].content, "let a = 1;\n");
+//!
+//! let joined = join(&split.manifest, |name| {
+//!     Ok(split.files.iter().find(|file| file.name == name).unwrap().content.clone())
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(joined, sections);
+//! ```
+
+use std::{borrow::Cow, collections::HashMap, io};
+
+use crate::{AttributeName, AttributeValue, Block, BlockName, Raw, Section};
+
+pub use self::error::JoinError;
+
+mod error {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::io;
+
+    /// Returned when [`join`][super::join] fails.
+    #[derive(Debug)]
+    #[non_exhaustive]
+    pub enum JoinError {
+        /// A block file could not be read.
+        Io { file: String, source: io::Error },
+        /// The manifest contains an invalid block name, attribute or raw section, or a block
+        /// whose content contains its end tag.
+        Invalid(crate::Error),
+    }
+
+    impl Display for JoinError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Io { file, source } => write!(f, "unable to read `{file}`: {source}"),
+                Self::Invalid(err) => write!(f, "invalid manifest: {err}"),
+            }
+        }
+    }
+
+    impl Error for JoinError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                Self::Io { source, .. } => Some(source),
+                Self::Invalid(err) => Some(err),
+            }
+        }
+    }
+
+    impl<T: Into<crate::Error>> From<T> for JoinError {
+        fn from(err: T) -> Self {
+            Self::Invalid(err.into())
+        }
+    }
+}
+
+/// The result of [`split`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Split {
+    /// Describes how to [`join`] the files back.
+    pub manifest: Manifest,
+    /// One file per block, in order.
+    pub files: Vec<SplitFile>,
+}
+
+/// A file containing the content of a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitFile {
+    /// The file name, i.e: `Component.style.scss`.
+    pub name: String,
+    pub content: String,
+}
+
+/// Lists the sections of a Vue SFC, in order.
+///
+/// Blocks refer to the file holding their content, raw sections are stored inline.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    pub sections: Vec<ManifestSection>,
+}
+
+/// See [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
+pub enum ManifestSection {
+    Block {
+        name: String,
+        attributes: Vec<(String, Option<String>)>,
+        file: String,
+    },
+    Raw {
+        content: String,
+    },
+}
+
+/// Split sections into one file per block.
+///
+/// Files are named `{stem}.{block name}.{extension}`, the block name lowercased and the extension
+/// chosen from the `lang` attribute when present, from the block name otherwise. When several
+/// blocks share the same name, an index is inserted before the extension, i.e:
+/// `Component.style.1.css`.
+pub fn split(stem: &str, sections: &[Section]) -> Split {
+    let mut manifest = Manifest::default();
+    let mut files = Vec::new();
+    let mut counts = HashMap::new();
+
+    for section in sections {
+        match section {
+            Section::Block(block) => {
+                let name = sanitize(&block.name.as_str().to_ascii_lowercase());
+                let count = counts.entry(name.clone()).or_insert(0_usize);
+                let extension = extension(block);
+
+                let file = if *count == 0 {
+                    format!("{stem}.{name}.{extension}")
+                } else {
+                    format!("{stem}.{name}.{count}.{extension}")
+                };
+
+                *count += 1;
+
+                let mut content = block.content.trim_end().to_owned();
+
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+
+                manifest.sections.push(ManifestSection::Block {
+                    name: block.name.as_str().to_owned(),
+                    attributes: block
+                        .attributes
+                        .iter()
+                        .map(|(name, value)| {
+                            (
+                                name.as_str().to_owned(),
+                                value.as_ref().map(|value| value.as_str().to_owned()),
+                            )
+                        })
+                        .collect(),
+                    file: file.clone(),
+                });
+
+                files.push(SplitFile {
+                    name: file,
+                    content,
+                });
+            }
+            Section::Raw(raw) => manifest.sections.push(ManifestSection::Raw {
+                content: raw.as_str().to_owned(),
+            }),
+        }
+    }
+
+    Split { manifest, files }
+}
+
+/// Join files described by a [`Manifest`] back into sections.
+///
+/// `read` is called with the file name of each block and must return its content.
+///
+/// Blocks & raw sections are built with [`Block::new`] & [`Raw::from_cow`], so that the joined
+/// sections print as markup which parses back to them.
+///
+/// # Errors
+/// Will return an error if `read` fails, if the manifest contains an invalid block name,
+/// attribute or raw section, or if a block content contains its end tag.
+pub fn join(
+    manifest: &Manifest,
+    mut read: impl FnMut(&str) -> io::Result<String>,
+) -> Result<Vec<Section<'static>>, JoinError> {
+    manifest
+        .sections
+        .iter()
+        .map(|section| match section {
+            ManifestSection::Block {
+                name,
+                attributes,
+                file,
+            } => {
+                let content = read(file).map_err(|source| JoinError::Io {
+                    file: file.clone(),
+                    source,
+                })?;
+
+                Ok(Section::Block(Block::new(
+                    BlockName::from_cow(Cow::Owned(name.clone()))?,
+                    attributes
+                        .iter()
+                        .map(|(name, value)| {
+                            Ok((
+                                AttributeName::from_cow(Cow::Owned(name.clone()))?,
+                                value
+                                    .as_ref()
+                                    .map(|value| {
+                                        AttributeValue::from_cow(Cow::Owned(value.clone()))
+                                    })
+                                    .transpose()?,
+                            ))
+                        })
+                        .collect::<Result<_, JoinError>>()?,
+                    Cow::Owned(content),
+                )?))
+            }
+            ManifestSection::Raw { content } => {
+                Ok(Section::Raw(Raw::from_cow(Cow::Owned(content.clone()))?))
+            }
+        })
+        .collect()
+}
+
+/// Replace characters that are not ASCII alphanumeric, `-` or `_`.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|ch| match ch {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => ch,
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Choose a file extension for the given block.
+fn extension(block: &Block) -> String {
+    let lang = block.attribute_value("lang").map(AttributeValue::as_str);
+    let name = block.name.as_str().to_ascii_lowercase();
+
+    match (name.as_str(), lang) {
+        (_, Some("stylus")) => String::from("styl"),
+        (_, Some("postcss")) => String::from("pcss"),
+        (_, Some("markdown")) => String::from("md"),
+        (_, Some(lang)) if !lang.is_empty() => sanitize(lang),
+        ("template", _) => String::from("html"),
+        ("script", _) => String::from("js"),
+        ("style", _) => String::from("css"),
+        ("i18n", _) => String::from("json"),
+        ("docs", _) => String::from("md"),
+        _ => String::from("txt"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join, split, JoinError, Manifest, ManifestSection};
+
+    #[test]
+    fn test_split_join() {
+        let sections = crate::parse(concat!(
+            "<!-- header -->\n",
+            "<template><div/></template>\n",
+            "<script setup lang=\"ts\">const a = 1</script>\n",
+            "<style scoped lang=\"scss\">a {}</style>\n",
+            "<style>b {}</style>\n",
+            "<i18n locale=\"en\">{}</i18n>\n",
+            "<docs lang=\"stylus\"></docs>",
+        ))
+        .unwrap();
+
+        let split = split("Component", &sections);
+
+        assert_eq!(
+            split
+                .files
+                .iter()
+                .map(|file| file.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Component.template.html",
+                "Component.script.ts",
+                "Component.style.scss",
+                "Component.style.1.css",
+                "Component.i18n.json",
+                "Component.docs.styl",
+            ]
+        );
+        assert_eq!(split.files[5].content, "");
+        assert_eq!(
+            split.manifest.sections[0],
+            ManifestSection::Raw {
+                content: String::from("<!-- header -->")
+            }
+        );
+
+        let joined = join(&split.manifest, |name| {
+            Ok(split
+                .files
+                .iter()
+                .find(|file| file.name == name)
+                .unwrap()
+                .content
+                .clone())
+        })
+        .unwrap();
+
+        assert_eq!(joined, sections);
+    }
+
+    #[test]
+    fn test_split_case_insensitive() {
+        let split = split(
+            "Component",
+            &crate::parse("<style></style><STYLE></STYLE><Style></Style>").unwrap(),
+        );
+
+        assert_eq!(
+            split
+                .files
+                .iter()
+                .map(|file| file.name.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Component.style.css",
+                "Component.style.1.css",
+                "Component.style.2.css",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_join_invalid_block() {
+        let split = split("Component", &crate::parse("<script></script>").unwrap());
+
+        assert!(matches!(
+            join(&split.manifest, |_| Ok(String::from("'</script>'"))),
+            Err(JoinError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_join_invalid_raw() {
+        let manifest = Manifest {
+            sections: vec![ManifestSection::Raw {
+                content: String::from("<template>x</template>"),
+            }],
+        };
+
+        assert!(matches!(
+            join(&manifest, |_| unreachable!()),
+            Err(JoinError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_join_missing_file() {
+        let split = split("Component", &crate::parse("<script></script>").unwrap());
+
+        assert!(join(
+            &split.manifest,
+            |_| Err(std::io::ErrorKind::NotFound.into())
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! Traversal of the AST.
+//!
+//! - [`Visit`] walks a borrowed AST, i.e: to collect information.
+//! - [`VisitMut`] walks a mutably borrowed AST, i.e: to edit it in place.
+//! - [`Fold`] consumes an AST and produces a transformed one.
+//!
+//! Each method defaults to the matching `walk_*` (or `fold_*`) function, which visits the
+//! children of the node. Overriding a method and calling that function from it is how a visitor
+//! keeps descending into the node.
+//!
+//! # Example
+//! ```rust
+//! use vue_sfc::{visit::Visit, Block};
+//!
+//! #[derive(Default)]
+//! struct BlockNames(Vec<String>);
+//!
+//! impl Visit<'_> for BlockNames {
+//!     fn visit_block(&mut self, block: &Block<'_>) {
+//!         self.0.push(block.name.to_string());
+//!     }
+//! }
+//!
+//! let sfc = vue_sfc::parse("<template></template>\n<script></script>").unwrap();
+//!
+//! let mut names = BlockNames::default();
+//! names.visit_sections(&sfc);
+//!
+//! assert_eq!(names.0, vec!["template", "script"]);
+//! ```
+
+use crate::{Attribute, Block, Raw, Section};
+
+/// Walks a borrowed AST, see the [module documentation](self).
+pub trait Visit<'a> {
+    fn visit_sections(&mut self, sections: &[Section<'a>]) {
+        walk_sections(self, sections);
+    }
+
+    fn visit_section(&mut self, section: &Section<'a>) {
+        walk_section(self, section);
+    }
+
+    fn visit_block(&mut self, block: &Block<'a>) {
+        walk_block(self, block);
+    }
+
+    fn visit_attribute(&mut self, _attribute: &Attribute<'a>) {}
+
+    fn visit_raw(&mut self, _raw: &Raw<'a>) {}
+}
+
+pub fn walk_sections<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, sections: &[Section<'a>]) {
+    for section in sections {
+        visitor.visit_section(section);
+    }
+}
+
+pub fn walk_section<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, section: &Section<'a>) {
+    match section {
+        Section::Raw(raw) => visitor.visit_raw(raw),
+        Section::Block(block) => visitor.visit_block(block),
+    }
+}
+
+pub fn walk_block<'a, V: Visit<'a> + ?Sized>(visitor: &mut V, block: &Block<'a>) {
+    for attribute in &block.attributes {
+        visitor.visit_attribute(attribute);
+    }
+}
+
+/// Walks a mutably borrowed AST, see the [module documentation](self).
+pub trait VisitMut<'a> {
+    /// Sections can be added or removed here.
+    fn visit_sections_mut(&mut self, sections: &mut Vec<Section<'a>>) {
+        walk_sections_mut(self, sections);
+    }
+
+    fn visit_section_mut(&mut self, section: &mut Section<'a>) {
+        walk_section_mut(self, section);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block<'a>) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_attribute_mut(&mut self, _attribute: &mut Attribute<'a>) {}
+
+    fn visit_raw_mut(&mut self, _raw: &mut Raw<'a>) {}
+}
+
+pub fn walk_sections_mut<'a, V: VisitMut<'a> + ?Sized>(
+    visitor: &mut V,
+    sections: &mut Vec<Section<'a>>,
+) {
+    for section in sections {
+        visitor.visit_section_mut(section);
+    }
+}
+
+pub fn walk_section_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, section: &mut Section<'a>) {
+    match section {
+        Section::Raw(raw) => visitor.visit_raw_mut(raw),
+        Section::Block(block) => visitor.visit_block_mut(block),
+    }
+}
+
+pub fn walk_block_mut<'a, V: VisitMut<'a> + ?Sized>(visitor: &mut V, block: &mut Block<'a>) {
+    for attribute in &mut block.attributes {
+        visitor.visit_attribute_mut(attribute);
+    }
+}
+
+/// Consumes an AST and produces a transformed one, see the [module documentation](self).
+pub trait Fold<'a> {
+    /// Sections can be added or removed here.
+    fn fold_sections(&mut self, sections: Vec<Section<'a>>) -> Vec<Section<'a>> {
+        fold_sections(self, sections)
+    }
+
+    fn fold_section(&mut self, section: Section<'a>) -> Section<'a> {
+        fold_section(self, section)
+    }
+
+    fn fold_block(&mut self, block: Block<'a>) -> Block<'a> {
+        fold_block(self, block)
+    }
+
+    fn fold_attribute(&mut self, attribute: Attribute<'a>) -> Attribute<'a> {
+        attribute
+    }
+
+    fn fold_raw(&mut self, raw: Raw<'a>) -> Raw<'a> {
+        raw
+    }
+}
+
+pub fn fold_sections<'a, F: Fold<'a> + ?Sized>(
+    folder: &mut F,
+    sections: Vec<Section<'a>>,
+) -> Vec<Section<'a>> {
+    sections
+        .into_iter()
+        .map(|section| folder.fold_section(section))
+        .collect()
+}
+
+pub fn fold_section<'a, F: Fold<'a> + ?Sized>(folder: &mut F, section: Section<'a>) -> Section<'a> {
+    match section {
+        Section::Raw(raw) => Section::Raw(folder.fold_raw(raw)),
+        Section::Block(block) => Section::Block(folder.fold_block(block)),
+    }
+}
+
+pub fn fold_block<'a, F: Fold<'a> + ?Sized>(folder: &mut F, block: Block<'a>) -> Block<'a> {
+    Block {
+        attributes: block
+            .attributes
+            .into_iter()
+            .map(|attribute| folder.fold_attribute(attribute))
+            .collect(),
+        ..block
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{Attribute, AttributeValue, Block, Raw, Section};
+
+    use super::{walk_block, Fold, Visit, VisitMut};
+
+    #[test]
+    fn test_visit() {
+        #[derive(Default)]
+        struct Counter {
+            blocks: usize,
+            attributes: usize,
+            raws: usize,
+        }
+
+        impl Visit<'_> for Counter {
+            fn visit_block(&mut self, block: &Block<'_>) {
+                self.blocks += 1;
+                walk_block(self, block);
+            }
+
+            fn visit_attribute(&mut self, _attribute: &Attribute<'_>) {
+                self.attributes += 1;
+            }
+
+            fn visit_raw(&mut self, _raw: &Raw<'_>) {
+                self.raws += 1;
+            }
+        }
+
+        let sfc =
+            crate::parse("<!-- a -->\n<script setup lang=\"ts\"></script>\n<style scoped></style>")
+                .unwrap();
+
+        let mut counter = Counter::default();
+        counter.visit_sections(&sfc);
+
+        assert_eq!(
+            (counter.blocks, counter.attributes, counter.raws),
+            (2, 3, 1)
+        );
+    }
+
+    #[test]
+    fn test_visit_mut() {
+        struct StripComments;
+
+        impl VisitMut<'_> for StripComments {
+            fn visit_sections_mut(&mut self, sections: &mut Vec<Section<'_>>) {
+                sections.retain(|section| matches!(section, Section::Block(_)));
+            }
+        }
+
+        struct TsToJs;
+
+        impl<'a> VisitMut<'a> for TsToJs {
+            fn visit_attribute_mut(&mut self, (name, value): &mut Attribute<'a>) {
+                if *name == "lang" && value.as_deref() == Some("ts") {
+                    *value = Some(AttributeValue::try_from("js").unwrap());
+                }
+            }
+        }
+
+        let mut sfc = crate::parse("<!-- a -->\n<script lang=\"ts\"></script>").unwrap();
+
+        StripComments.visit_sections_mut(&mut sfc);
+        TsToJs.visit_sections_mut(&mut sfc);
+
+        assert_eq!(sfc.len(), 1);
+        assert_eq!(sfc[0].to_string(), "<script lang=\"js\"></script>");
+    }
+
+    #[test]
+    fn test_fold() {
+        struct Uppercase;
+
+        impl<'a> Fold<'a> for Uppercase {
+            fn fold_block(&mut self, block: Block<'a>) -> Block<'a> {
+                Block {
+                    content: Cow::Owned(block.content.to_uppercase()),
+                    ..block
+                }
+            }
+        }
+
+        let sfc = crate::parse("<template>a</template>").unwrap();
+
+        assert_eq!(
+            Uppercase.fold_sections(sfc)[0].to_string(),
+            "<template>\nA\n</template>"
+        );
+    }
+}